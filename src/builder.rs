@@ -12,9 +12,19 @@
 //!
 //! ```rust,no_run
 //! use ssd1306_i2c::{displayrotation::DisplayRotation, displaysize::DisplaySize, Builder};
+//! # use embedded_hal::i2c::{ErrorType, I2c, Operation};
+//! # struct I2cStub;
+//! # impl ErrorType for I2cStub {
+//! #     type Error = core::convert::Infallible;
+//! # }
+//! # impl I2c for I2cStub {
+//! #     fn transaction(&mut self, _: u8, _: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+//! #         Ok(())
+//! #     }
+//! # }
 //!
-//! let i2c = /* I2C interface from your HAL of choice */
-
+//! // I2C interface from your HAL of choice
+//! let i2c = I2cStub;
 //!
 //! Builder::new()
 //!     .with_rotation(DisplayRotation::Rotate180)
@@ -28,14 +38,13 @@
 //! example, to use [`GraphicsMode` mode](../mode/graphics/struct.GraphicsMode.html):
 //!
 
-use core::marker::PhantomData;
 //use hal::{self, digital::v2::OutputPin};
-use embedded_hal;
+use embedded_hal::{self, delay::DelayNs};
 
 use crate::{
     displayrotation::DisplayRotation,
     displaysize::DisplaySize,
-    interface::I2cInterface,
+    interface::{I2cInterface, NoDelay, RetryPolicy},
     mode::{displaymode::DisplayMode, raw::RawMode},
     properties::DisplayProperties,
 };
@@ -47,7 +56,10 @@ use crate::{
 pub struct Builder {
     display_size: DisplaySize,
     rotation: DisplayRotation,
+    mirror: (bool, bool),
     i2c_addr: u8,
+    max_transfer: usize,
+    retry: RetryPolicy,
 }
 
 impl Default for Builder {
@@ -62,7 +74,10 @@ impl Builder {
         Builder {
             display_size: DisplaySize::Display128x64,
             rotation: DisplayRotation::Rotate0,
+            mirror: (false, false),
             i2c_addr: 0x3c,
+            max_transfer: I2cInterface::<()>::DEFAULT_MAX_TRANSFER,
+            retry: RetryPolicy::NONE,
         }
     }
 }
@@ -87,18 +102,61 @@ impl Builder {
         Self { rotation, ..self }
     }
 
+    /// Mirror the display horizontally and/or vertically, e.g. for panels viewed through a mirror
+    /// or prism. Mirroring is applied on top of the rotation. Defaults to no mirroring.
+    pub fn with_mirror(self, horizontal: bool, vertical: bool) -> Self {
+        Self {
+            mirror: (horizontal, vertical),
+            ..self
+        }
+    }
+
+    /// Set the largest number of data bytes sent in a single I2C write. Defaults to
+    /// [`I2cInterface::DEFAULT_MAX_TRANSFER`], one display row. Some HALs fail on writes longer than 32 or 64 bytes, others can send a full
+    /// frame at once.
+    pub fn with_max_transfer(self, max_transfer: usize) -> Self {
+        Self {
+            max_transfer,
+            ..self
+        }
+    }
+
+    /// Set the policy for retrying I2C transfers that fail with a transient error. Defaults to
+    /// no retries. Delays between attempts need a delay provider, see
+    /// [connect_i2c_with_delay](Builder::connect_i2c_with_delay).
+    pub fn with_retry(self, retry: RetryPolicy) -> Self {
+        Self { retry, ..self }
+    }
+
     /// Finish the builder and use I2C to communicate with the display
     pub fn connect_i2c<I2C>(self, i2c: I2C) -> DisplayMode<RawMode<I2cInterface<I2C>>>
     where
         I2C: embedded_hal::i2c::I2c,
+    {
+        self.connect_i2c_with_delay(i2c, NoDelay)
+    }
+
+    /// Finish the builder and use I2C to communicate with the display, waiting with `delay`
+    /// between retries of failed transfers
+    pub fn connect_i2c_with_delay<I2C, DELAY>(
+        self,
+        i2c: I2C,
+        delay: DELAY,
+    ) -> DisplayMode<RawMode<I2cInterface<I2C, DELAY>>>
+    where
+        I2C: embedded_hal::i2c::I2c,
+        DELAY: DelayNs,
     {
         let properties = DisplayProperties::new(
-            I2cInterface::new(i2c, self.i2c_addr),
+            I2cInterface::new(i2c, self.i2c_addr)
+                .with_max_transfer(self.max_transfer)
+                .with_retry(self.retry, delay),
             self.display_size,
             self.rotation,
             crate::command::AddrMode::Horizontal,
-        );
-        DisplayMode::<RawMode<I2cInterface<I2C>>>::new(properties)
+        )
+        .with_mirror(self.mirror.0, self.mirror.1);
+        DisplayMode::<RawMode<I2cInterface<I2C, DELAY>>>::new(properties)
     }
 }
 
@@ -0,0 +1,321 @@
+//! Burn-in protection
+//!
+//! Opt-in helpers for always-on panels. Once enabled on a
+//! [`GraphicsMode`](../mode/graphics/struct.GraphicsMode.html) with
+//! `enable_burn_in_protection`, the driver slowly orbits the image by a pixel or two, dims the
+//! panel after a period of unchanged content and can finally switch to a screensaver.
+//!
+//! Nothing happens on its own: the application calls `burn_in_tick(elapsed)` from its main loop.
+//! The unit of `elapsed` is up to the caller (milliseconds, seconds, frames...), all intervals in
+//! [`BurnInConfig`] are expressed in the same unit.
+
+use crate::brightness::Brightness;
+
+/// Pattern shown when the screensaver kicks in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Screensaver {
+    /// Turn the panel off
+    DisplayOff,
+    /// Show a sparse dot pattern that moves on every tick of the pixel shift interval
+    Dots,
+}
+
+/// Burn-in protection settings. An interval of 0 disables the matching feature.
+#[derive(Clone, Copy, Debug)]
+pub struct BurnInConfig {
+    shift_interval: u32,
+    max_shift: u8,
+    dim_after: u32,
+    dim_brightness: Brightness,
+    screensaver_after: u32,
+    screensaver: Screensaver,
+}
+
+impl Default for BurnInConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BurnInConfig {
+    /// Create a new configuration with every feature disabled
+    pub const fn new() -> Self {
+        BurnInConfig {
+            shift_interval: 0,
+            max_shift: 1,
+            dim_after: 0,
+            dim_brightness: Brightness::DIMMEST,
+            screensaver_after: 0,
+            screensaver: Screensaver::DisplayOff,
+        }
+    }
+
+    /// Move the image every `interval` ticks, by at most `max_shift` pixels (1 or 2) in each
+    /// direction
+    pub const fn with_pixel_shift(self, interval: u32, max_shift: u8) -> Self {
+        let max_shift = if max_shift > 2 { 2 } else { max_shift };
+        Self {
+            shift_interval: interval,
+            max_shift,
+            ..self
+        }
+    }
+
+    /// Switch to `brightness` once the content has not changed for `after` ticks
+    pub const fn with_auto_dim(self, after: u32, brightness: Brightness) -> Self {
+        Self {
+            dim_after: after,
+            dim_brightness: brightness,
+            ..self
+        }
+    }
+
+    /// Show `screensaver` once the content has not changed for `after` ticks
+    pub const fn with_screensaver(self, after: u32, screensaver: Screensaver) -> Self {
+        Self {
+            screensaver_after: after,
+            screensaver,
+            ..self
+        }
+    }
+
+    /// Get the brightness used while dimmed
+    pub fn dim_brightness(&self) -> Brightness {
+        self.dim_brightness
+    }
+
+    /// Get the configured screensaver pattern
+    pub fn screensaver(&self) -> Screensaver {
+        self.screensaver
+    }
+}
+
+/// Unit directions of one orbit ring, scaled by the ring number
+const ORBIT: [(i8, i8); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// What the display mode has to do after a tick
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct BurnInActions {
+    /// The pixel offset changed
+    pub shift: bool,
+    /// The panel should be dimmed
+    pub dim: bool,
+    /// The screensaver should be started, or its frame advanced
+    pub screensaver: bool,
+}
+
+/// Runtime state of the burn-in protection
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BurnInState {
+    config: BurnInConfig,
+    shift_elapsed: u32,
+    idle: u32,
+    step: u8,
+    offset: (i8, i8),
+    dimmed: bool,
+    screensaver: bool,
+    changed: bool,
+}
+
+impl BurnInState {
+    pub(crate) fn new(config: BurnInConfig) -> Self {
+        BurnInState {
+            config,
+            shift_elapsed: 0,
+            idle: 0,
+            step: 0,
+            offset: (0, 0),
+            dimmed: false,
+            screensaver: false,
+            changed: false,
+        }
+    }
+
+    pub(crate) fn config(&self) -> &BurnInConfig {
+        &self.config
+    }
+
+    /// Current (x, y) pixel offset
+    pub(crate) fn offset(&self) -> (i8, i8) {
+        self.offset
+    }
+
+    /// Current pixel shift step, also used to animate the screensaver
+    pub(crate) fn step(&self) -> u8 {
+        self.step
+    }
+
+    pub(crate) fn is_dimmed(&self) -> bool {
+        self.dimmed
+    }
+
+    pub(crate) fn is_screensaver(&self) -> bool {
+        self.screensaver
+    }
+
+    /// Record that the framebuffer content changed
+    pub(crate) fn mark_changed(&mut self) {
+        self.changed = true;
+    }
+
+    /// Consume the changed flag. When the content changed, the idle timer restarts and the dim
+    /// and screensaver states are left; the previous (dimmed, screensaver) states are returned.
+    pub(crate) fn take_changed(&mut self) -> Option<(bool, bool)> {
+        if !self.changed {
+            return None;
+        }
+        let woken = (self.dimmed, self.screensaver);
+        self.changed = false;
+        self.idle = 0;
+        self.dimmed = false;
+        self.screensaver = false;
+        Some(woken)
+    }
+
+    pub(crate) fn tick(&mut self, elapsed: u32) -> BurnInActions {
+        let mut actions = BurnInActions::default();
+
+        if self.config.shift_interval > 0 {
+            self.shift_elapsed = self.shift_elapsed.saturating_add(elapsed);
+            if self.shift_elapsed >= self.config.shift_interval {
+                self.shift_elapsed = 0;
+
+                let positions = 1 + ORBIT.len() as u8 * self.config.max_shift;
+                self.step = (self.step + 1) % positions;
+                self.offset = match self.step {
+                    0 => (0, 0),
+                    n => {
+                        let ring = ((n - 1) / ORBIT.len() as u8 + 1) as i8;
+                        let (dx, dy) = ORBIT[(n as usize - 1) % ORBIT.len()];
+                        (dx * ring, dy * ring)
+                    }
+                };
+                actions.shift = true;
+                actions.screensaver = self.screensaver;
+            }
+        }
+
+        self.idle = self.idle.saturating_add(elapsed);
+        if self.config.dim_after > 0 && !self.dimmed && self.idle >= self.config.dim_after {
+            self.dimmed = true;
+            actions.dim = true;
+        }
+        if self.config.screensaver_after > 0
+            && !self.screensaver
+            && self.idle >= self.config.screensaver_after
+        {
+            self.screensaver = true;
+            actions.screensaver = true;
+        }
+
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shift until the orbit is back at the origin, collecting every offset on the way
+    fn orbit(max_shift: u8) -> [(i8, i8); 17] {
+        let mut state = BurnInState::new(BurnInConfig::new().with_pixel_shift(1, max_shift));
+        let mut offsets = [(0, 0); 17];
+        for offset in offsets.iter_mut() {
+            assert!(state.tick(1).shift);
+            *offset = state.offset();
+        }
+        offsets
+    }
+
+    #[test]
+    fn pixel_shift_waits_for_the_interval() {
+        let mut state = BurnInState::new(BurnInConfig::new().with_pixel_shift(10, 1));
+
+        assert!(!state.tick(4).shift);
+        assert!(!state.tick(5).shift);
+        assert_eq!(state.offset(), (0, 0));
+        assert!(state.tick(1).shift);
+        assert_eq!(state.offset(), (1, 0));
+
+        // Elapsed time past the interval is not carried over
+        assert!(state.tick(15).shift);
+        assert!(!state.tick(9).shift);
+        assert_eq!(state.offset(), (1, 1));
+    }
+
+    #[test]
+    fn pixel_shift_orbits_the_origin() {
+        let offsets = orbit(1);
+        assert_eq!(offsets[..8], ORBIT);
+        assert_eq!(offsets[8], (0, 0));
+        assert_eq!(offsets[9], (1, 0));
+
+        let offsets = orbit(2);
+        assert_eq!(offsets[..8], ORBIT);
+        for (offset, (dx, dy)) in offsets[8..16].iter().zip(ORBIT) {
+            assert_eq!(*offset, (dx * 2, dy * 2));
+        }
+        assert_eq!(offsets[16], (0, 0));
+    }
+
+    #[test]
+    fn idle_content_dims_then_starts_the_screensaver() {
+        let config = BurnInConfig::new()
+            .with_auto_dim(10, Brightness::DIMMEST)
+            .with_screensaver(20, Screensaver::Dots);
+        let mut state = BurnInState::new(config);
+
+        assert!(!state.tick(9).dim);
+        assert!(state.tick(1).dim);
+        assert!(state.is_dimmed());
+        // Dimming is only requested once
+        assert!(!state.tick(5).dim);
+        assert!(!state.tick(4).screensaver);
+        assert!(state.tick(1).screensaver);
+        assert!(state.is_screensaver());
+        assert!(!state.tick(100).screensaver);
+
+        assert_eq!(state.take_changed(), None);
+        state.mark_changed();
+        assert_eq!(state.take_changed(), Some((true, true)));
+        assert!(!state.is_dimmed() && !state.is_screensaver());
+        assert_eq!(state.take_changed(), None);
+
+        // The idle timer restarted
+        assert!(!state.tick(9).dim);
+        assert!(state.tick(1).dim);
+    }
+
+    #[test]
+    fn screensaver_advances_with_the_pixel_shift() {
+        let config = BurnInConfig::new()
+            .with_pixel_shift(5, 1)
+            .with_screensaver(3, Screensaver::Dots);
+        let mut state = BurnInState::new(config);
+
+        let actions = state.tick(3);
+        assert!(actions.screensaver && !actions.shift);
+        let actions = state.tick(2);
+        assert!(actions.screensaver && actions.shift);
+        assert_eq!(state.step(), 1);
+        assert!(!state.tick(1).screensaver);
+    }
+
+    #[test]
+    fn disabled_features_never_trigger() {
+        let mut state = BurnInState::new(BurnInConfig::new());
+
+        let actions = state.tick(u32::MAX);
+        assert!(!actions.shift && !actions.dim && !actions.screensaver);
+        assert_eq!(state.offset(), (0, 0));
+    }
+}
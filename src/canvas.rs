@@ -0,0 +1,135 @@
+//! Off-screen drawing canvas
+//!
+//! A [`Canvas`] is a small frame buffer in the same page-packed layout as the
+//! [`GraphicsMode`](crate::mode::GraphicsMode) buffer: pages of 8 pixel rows, one byte per column,
+//! bit 0 being the top row of the page. UI pieces can be drawn into a canvas once and pasted into
+//! the display with [`GraphicsMode::blit`](crate::mode::GraphicsMode::blit).
+//!
+//! The height is given in pages, as array lengths can't be computed from const generics. A
+//! `Canvas<32, 2>` is 32 pixels wide and 16 pixels high.
+//!
+//! ```rust,ignore
+//! use ssd1306_i2c::{canvas::Canvas, rasterop::RasterOp};
+//!
+//! let mut icon: Canvas<16, 2> = Canvas::new();
+//! Circle::new(Point::zero(), 16)
+//!     .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+//!     .draw(&mut icon)
+//!     .unwrap();
+//!
+//! display.blit(&icon, icon.bounding_box(), Point::new(56, 24), RasterOp::Xor);
+//! display.flush().unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    image::GetPixel,
+    pixelcolor::BinaryColor,
+    Pixel,
+};
+
+/// Off-screen frame buffer `W` pixels wide and `PAGES` pages (8 pixel rows each) high
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Canvas<const W: usize, const PAGES: usize> {
+    pages: [[u8; W]; PAGES],
+}
+
+impl<const W: usize, const PAGES: usize> Default for Canvas<W, PAGES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const PAGES: usize> Canvas<W, PAGES> {
+    /// Create a canvas with all pixels off
+    pub const fn new() -> Self {
+        Canvas { pages: [[0; W]; PAGES] }
+    }
+
+    /// Width in pixels
+    pub const fn width(&self) -> u32 {
+        W as u32
+    }
+
+    /// Height in pixels
+    pub const fn height(&self) -> u32 {
+        PAGES as u32 * 8
+    }
+
+    /// Get the pages of the canvas, each `W` bytes long
+    pub fn pages(&self) -> &[[u8; W]; PAGES] {
+        &self.pages
+    }
+
+    /// Get the pages of the canvas for direct writing
+    pub fn pages_mut(&mut self) -> &mut [[u8; W]; PAGES] {
+        &mut self.pages
+    }
+
+    /// Turn all pixels off
+    pub fn clear(&mut self) {
+        self.pages = [[0; W]; PAGES];
+    }
+
+    /// Turn a pixel on or off. Pixels outside the canvas are ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: bool) {
+        if x >= self.width() || y >= self.height() {
+            return;
+        }
+
+        let byte = &mut self.pages[y as usize / 8][x as usize];
+        if value {
+            *byte |= 1 << (y % 8);
+        } else {
+            *byte &= !(1 << (y % 8));
+        }
+    }
+
+    /// Get the color of a pixel, or `None` if it is outside the canvas
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<BinaryColor> {
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+
+        let byte = self.pages[y as usize / 8][x as usize];
+        Some(BinaryColor::from(byte & (1 << (y % 8)) != 0))
+    }
+}
+
+impl<const W: usize, const PAGES: usize> DrawTarget for Canvas<W, PAGES> {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bb = self.bounding_box();
+
+        pixels
+            .into_iter()
+            .filter(|Pixel(pos, _color)| bb.contains(*pos))
+            .for_each(|Pixel(pos, color)| self.set_pixel(pos.x as u32, pos.y as u32, color.is_on()));
+
+        Ok(())
+    }
+}
+
+impl<const W: usize, const PAGES: usize> GetPixel for Canvas<W, PAGES> {
+    type Color = BinaryColor;
+
+    fn pixel(&self, p: Point) -> Option<Self::Color> {
+        if p.x < 0 || p.y < 0 {
+            return None;
+        }
+
+        self.get_pixel(p.x as u32, p.y as u32)
+    }
+}
+
+impl<const W: usize, const PAGES: usize> OriginDimensions for Canvas<W, PAGES> {
+    fn size(&self) -> Size {
+        Size::new(self.width(), self.height())
+    }
+}
@@ -18,6 +18,9 @@ pub enum Command {
     ColumnAddressLow(u8),
     /// Set column address higher 4 bits
     ColumnAddressHigh(u8),
+    /// Set column start and end address
+    /// This is only for horizontal or vertical addressing mode
+    ColumnAddress(u8, u8),
     /// Set page address
     /// This is only for horizontal or vertical addressing mode
     PageAddress(Page, Page),
@@ -55,6 +58,8 @@ pub enum Command {
     /// Set addressing mode;  OJS from SSD1306
     AddressMode(AddrMode),
     EnableScroll(bool),
+    /// Enable zoom-in mode, doubling every row of the displayed area
+    ZoomIn(bool),
 }
 
 impl Command {
@@ -63,14 +68,22 @@ impl Command {
     where
         DI: DisplayInterface,
     {
-        // Transform command into a fixed size array of 7 u8 and the real length for sending
-        let (data, len) = match self {
+        let (data, len) = self.encode();
+
+        // Send command over the interface
+        send_commands(iface, &data[0..len])
+    }
+
+    /// Transform command into a fixed size array of 7 u8 and the real length for sending
+    fn encode(self) -> ([u8; 7], usize) {
+        match self {
             Command::Contrast(val) => ([0x81, val, 0, 0, 0, 0, 0], 2),
             Command::AllOn(on) => ([0xA4 | (on as u8), 0, 0, 0, 0, 0, 0], 1),
             Command::Invert(inv) => ([0xA6 | (inv as u8), 0, 0, 0, 0, 0, 0], 1),
             Command::DisplayOn(on) => ([0xAE | (on as u8), 0, 0, 0, 0, 0, 0], 1),
             Command::ColumnAddressLow(addr) => ([0xF & addr, 0, 0, 0, 0, 0, 0], 1),
             Command::ColumnAddressHigh(addr) => ([0x10 | (0xF & addr), 0, 0, 0, 0, 0, 0], 1),
+            Command::ColumnAddress(start, end) => ([0x21, start, end, 0, 0, 0, 0], 3),
             Command::PageAddress(start, end) => ([0x22, start as u8, end as u8 ,0, 0, 0, 0], 3),            Command::PageStart(page) => ([0xB0 | (page as u8), 0, 0, 0, 0, 0, 0], 1),
             Command::StartLine(line) => ([0x40 | (0x3F & line), 0, 0, 0, 0, 0, 0], 1),
             Command::SegmentRemap(remap) => ([0xA0 | (remap as u8), 0, 0, 0, 0, 0, 0], 1),
@@ -95,11 +108,73 @@ impl Command {
             Command::Vpp9VSet() => ([0x33, 0, 0, 0, 0, 0, 0],1), // OJS: added this
             Command::AddressMode(mode) => ([0x20, mode as u8, 0, 0, 0, 0, 0], 2),
             Command::EnableScroll(en) => ([0x2e | (en as u8), 0, 0, 0, 0, 0, 0] ,1),
-         };
+            Command::ZoomIn(en) => ([0xD6, en as u8, 0, 0, 0, 0, 0], 2),
+        }
+    }
+}
 
-        // Send command over the interface
-        iface.send_commands(&data[0..len])
+/// Size of the [CommandBatch] buffer in bytes
+const BATCH_LEN: usize = 48;
+
+/// A sequence of commands sent to the display in a single transfer
+pub struct CommandBatch {
+    data: [u8; BATCH_LEN],
+    len: usize,
+}
+
+impl CommandBatch {
+    /// Create an empty batch
+    pub fn new() -> Self {
+        CommandBatch {
+            data: [0; BATCH_LEN],
+            len: 0,
+        }
     }
+
+    /// Append a command to the batch
+    pub fn push(&mut self, command: Command) -> &mut Self {
+        let (data, len) = command.encode();
+        debug_assert!(self.len + len <= BATCH_LEN, "Command batch overflow");
+
+        self.data[self.len..self.len + len].copy_from_slice(&data[..len]);
+        self.len += len;
+        self
+    }
+
+    /// Send all commands in the batch to ssd1306
+    pub fn send<DI>(&self, iface: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: DisplayInterface,
+    {
+        send_commands(iface, &self.data[..self.len])
+    }
+}
+
+/// NOP command
+const NOP: u8 = 0xE3;
+
+/// Send a command stream, retrying it from the start as allowed by the interface. A failed
+/// transfer may have been partly acknowledged, leaving a command waiting for its arguments, so
+/// enough NOPs to complete the longest command are sent first. Any setting the NOPs end up in is
+/// overwritten by the repeated commands.
+pub(crate) fn send_commands<DI>(iface: &mut DI, cmds: &[u8]) -> Result<(), DI::Error>
+where
+    DI: DisplayInterface,
+{
+    let mut attempt = 1;
+    let mut result = iface.send_commands(cmds);
+    while let Err(error) = result {
+        if !iface.retry(&error, attempt) {
+            return Err(error);
+        }
+        attempt = attempt.saturating_add(1);
+
+        result = iface
+            .send_commands(&[NOP; 6])
+            .and_then(|()| iface.send_commands(cmds));
+    }
+
+    Ok(())
 }
 
 /// Display page
@@ -175,9 +250,8 @@ pub enum VcomhLevel {
     Auto = 0b100,
 }
 
-/// Address mode;  from SSD1306
+/// Memory addressing mode;  from SSD1306
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(dead_code)]
 pub enum AddrMode {
     /// Horizontal mode
     Horizontal = 0b00,
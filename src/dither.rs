@@ -0,0 +1,267 @@
+//! Dithering adapter for grayscale and color content
+//!
+//! [`GraphicsMode`] only accepts [`BinaryColor`]. A [`DitheringAdapter`] borrows a `GraphicsMode`
+//! and accepts any color that converts to [`Gray8`], e.g. `Gray4`, `Gray8` or `Rgb565`, so
+//! embedded-graphics images and primitives in those formats can be drawn directly.
+//!
+//! ```rust,ignore
+//! use ssd1306_i2c::dither::{Dithering, DitheringAdapter};
+//!
+//! let mut adapter = DitheringAdapter::new(&mut display, Dithering::FloydSteinberg);
+//! Image::new(&photo, Point::zero()).draw(&mut adapter).unwrap();
+//! display.flush().unwrap();
+//! ```
+
+use core::marker::PhantomData;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    pixelcolor::{BinaryColor, Gray8, GrayColor, PixelColor},
+    primitives::{PointsIter, Rectangle},
+    Pixel,
+};
+
+use crate::{interface::DisplayInterface, mode::GraphicsMode};
+
+/// Largest area width, in pixels, that error diffusion is applied over. Pixels further right in
+/// wider areas fall back to ordered dithering.
+const ERROR_ROW_LEN: usize = 132;
+
+/// 4x4 Bayer matrix used for ordered dithering
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Dithering method
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dithering {
+    /// Pixels at or above the given luma (0-255) are on, all others off
+    Threshold(u8),
+    /// Ordered dithering with a 4x4 Bayer matrix
+    Ordered,
+    /// Floyd-Steinberg error diffusion. Only applies to contiguous fills, like images and filled
+    /// rectangles, where the pixel order is known. Individual pixels use ordered dithering.
+    FloydSteinberg,
+}
+
+/// A `DrawTarget` accepting grayscale or color content, dithered onto a [`GraphicsMode`]
+pub struct DitheringAdapter<'a, DI, C>
+where
+    DI: DisplayInterface,
+{
+    target: &'a mut GraphicsMode<DI>,
+    method: Dithering,
+    color: PhantomData<C>,
+}
+
+impl<'a, DI, C> DitheringAdapter<'a, DI, C>
+where
+    DI: DisplayInterface,
+    C: PixelColor + Into<Gray8>,
+{
+    /// Wrap a `GraphicsMode` using the given dithering method
+    pub fn new(target: &'a mut GraphicsMode<DI>, method: Dithering) -> Self {
+        DitheringAdapter {
+            target,
+            method,
+            color: PhantomData,
+        }
+    }
+
+    /// Change the dithering method
+    pub fn set_method(&mut self, method: Dithering) {
+        self.method = method;
+    }
+
+    /// Get the dithering method
+    pub fn method(&self) -> Dithering {
+        self.method
+    }
+
+    /// Threshold a single pixel with the configured method. Floyd-Steinberg falls back to
+    /// ordered dithering.
+    fn quantize(&self, pos: Point, luma: u8) -> BinaryColor {
+        let threshold = match self.method {
+            Dithering::Threshold(threshold) => threshold,
+            Dithering::Ordered | Dithering::FloydSteinberg => {
+                BAYER_4X4[(pos.y & 3) as usize][(pos.x & 3) as usize] * 16 + 8
+            }
+        };
+
+        (luma >= threshold).into()
+    }
+
+    fn put(&mut self, pos: Point, color: BinaryColor) {
+        if self.target.bounding_box().contains(pos) {
+            self.target
+                .set_pixel(pos.x as u32, pos.y as u32, color.is_on().into());
+        }
+    }
+}
+
+impl<DI, C> DrawTarget for DitheringAdapter<'_, DI, C>
+where
+    DI: DisplayInterface,
+    C: PixelColor + Into<Gray8>,
+{
+    type Color = C;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(pos, color) in pixels {
+            let color = self.quantize(pos, color.into().luma());
+            self.put(pos, color);
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if self.method != Dithering::FloydSteinberg {
+            return self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(pos, color)| Pixel(pos, color)),
+            );
+        }
+
+        // Error of the current and the next row, offset by one to allow diffusing to the left
+        let mut errors = [[0i16; ERROR_ROW_LEN + 2]; 2];
+        let mut current = 0;
+        let width = area.size.width as usize;
+
+        for (i, (pos, color)) in area.points().zip(colors).enumerate() {
+            let column = i % width;
+            if column == 0 && i > 0 {
+                errors[current] = [0; ERROR_ROW_LEN + 2];
+                current ^= 1;
+            }
+
+            let luma = color.into().luma();
+            if column >= ERROR_ROW_LEN {
+                let color = self.quantize(pos, luma);
+                self.put(pos, color);
+                continue;
+            }
+
+            let value = luma as i16 + errors[current][column + 1];
+            let on = value >= 128;
+            let error = value - if on { 255 } else { 0 };
+            // The last share takes the rounding remainder, so the whole error is passed on and
+            // the gray level does not drift
+            let (right, below_left, below) = (error * 7 / 16, error * 3 / 16, error * 5 / 16);
+            errors[current][column + 2] += right;
+            errors[current ^ 1][column] += below_left;
+            errors[current ^ 1][column + 1] += below;
+            errors[current ^ 1][column + 2] += error - right - below_left - below;
+
+            self.put(pos, BinaryColor::from(on));
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, C> OriginDimensions for DitheringAdapter<'_, DI, C>
+where
+    DI: DisplayInterface,
+{
+    fn size(&self) -> Size {
+        self.target.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        command::AddrMode, displayrotation::DisplayRotation, displaysize::DisplaySize,
+        interface::mock::MockInterface, mode::displaymode::DisplayModeTrait,
+        properties::DisplayProperties,
+    };
+    use core::ops::Range;
+
+    fn display() -> GraphicsMode<MockInterface> {
+        let (iface, _state) = MockInterface::new();
+
+        GraphicsMode::new(DisplayProperties::new(
+            iface,
+            DisplaySize::Display128x64NoOffset,
+            DisplayRotation::Rotate0,
+            AddrMode::Horizontal,
+        ))
+    }
+
+    fn count_on(display: &GraphicsMode<MockInterface>, x: Range<u32>, y: u32) -> usize {
+        x.filter(|&x| display.get_pixel(x, y) == Some(BinaryColor::On))
+            .count()
+    }
+
+    /// Check that every band of two rows within `columns` has the share of pixels on that
+    /// matches `luma`, so the error does not build up from row to row. Single rows may alternate
+    /// between denser and sparser patterns.
+    fn assert_gray_level(display: &GraphicsMode<MockInterface>, columns: Range<u32>, luma: u8) {
+        let band = 2 * columns.len();
+        let expected = band * luma as usize / 255;
+
+        let mut total = 0;
+        for y in (0..64).step_by(2) {
+            let on =
+                count_on(display, columns.clone(), y) + count_on(display, columns.clone(), y + 1);
+            assert!(
+                on.abs_diff(expected) <= band / 24,
+                "luma {luma} rows {y}: {on}"
+            );
+            total += on;
+        }
+        assert!(
+            total.abs_diff(32 * expected) <= 32 * band / 100,
+            "luma {luma}: {total}"
+        );
+    }
+
+    #[test]
+    fn error_diffusion_keeps_the_gray_level_down_the_area() {
+        for luma in [64, 128, 192] {
+            let mut display = display();
+            let area = display.bounding_box();
+            DitheringAdapter::new(&mut display, Dithering::FloydSteinberg)
+                .fill_solid(&area, Gray8::new(luma))
+                .unwrap();
+
+            assert_gray_level(&display, 0..128, luma);
+        }
+    }
+
+    #[test]
+    fn wide_areas_fall_back_to_ordered_dithering() {
+        // The area is 200 pixels wide, the display shows its columns 72 to 199
+        let area = Rectangle::new(Point::new(-72, 0), Size::new(200, 64));
+        let split = (ERROR_ROW_LEN as i32 - 72) as u32;
+
+        let mut diffused = display();
+        DitheringAdapter::new(&mut diffused, Dithering::FloydSteinberg)
+            .fill_solid(&area, Gray8::new(128))
+            .unwrap();
+        let mut ordered = display();
+        DitheringAdapter::new(&mut ordered, Dithering::Ordered)
+            .fill_solid(&area, Gray8::new(128))
+            .unwrap();
+
+        for y in 0..64 {
+            for x in split..128 {
+                assert_eq!(
+                    diffused.get_pixel(x, y),
+                    ordered.get_pixel(x, y),
+                    "({x}, {y})"
+                );
+            }
+        }
+        assert_gray_level(&diffused, 0..split, 128);
+    }
+}
@@ -1,14 +1,87 @@
 //! SSD1306 I2C Interface
 
-use embedded_hal::{delay::DelayNs, i2c::I2c, i2c::ErrorType};
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{Error as _, ErrorKind, ErrorType, I2c, Operation},
+};
 
 use super::DisplayInterface;
-use crate::{command::Page, Error};
+use crate::Error;
+
+/// Delay provider that does not wait at all, used when no delay is given for retries
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Policy for retrying I2C transfers that fail with a transient error
+///
+/// A write that fails after some bytes were acknowledged leaves the display part way through a
+/// command or with its RAM pointer advanced. Failed writes are therefore not repeated as is:
+/// commands are resynchronised and sent again from the start, and data is resumed after setting
+/// the draw address again, see [`DisplayProperties::draw`](crate::properties::DisplayProperties::draw).
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u8,
+    delay_us: u32,
+    retry_on: fn(ErrorKind) -> bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry, every transfer is attempted once
+    pub const NONE: RetryPolicy = RetryPolicy::new(1);
+
+    /// Attempt every transfer up to `max_attempts` times. By default NACKs, arbitration loss and
+    /// bus errors are retried without delay in between.
+    pub const fn new(max_attempts: u8) -> Self {
+        RetryPolicy {
+            max_attempts: if max_attempts == 0 { 1 } else { max_attempts },
+            delay_us: 0,
+            retry_on: Self::is_transient,
+        }
+    }
+
+    /// Wait `delay_us` microseconds before each retry. Requires the interface to be given a delay
+    /// provider, see [`I2cInterface::with_retry`].
+    pub const fn with_delay_us(self, delay_us: u32) -> Self {
+        Self { delay_us, ..self }
+    }
+
+    /// Select which error kinds are retried. All others fail immediately.
+    pub const fn with_retry_on(self, retry_on: fn(ErrorKind) -> bool) -> Self {
+        Self { retry_on, ..self }
+    }
+
+    /// Default retry predicate: NACKs, arbitration loss and bus errors
+    pub fn is_transient(kind: ErrorKind) -> bool {
+        matches!(
+            kind,
+            ErrorKind::NoAcknowledge(_) | ErrorKind::ArbitrationLoss | ErrorKind::Bus
+        )
+    }
+}
 
 /// SSD1306 I2C communication interface
-pub struct I2cInterface<I2C> {
+pub struct I2cInterface<I2C, DELAY = NoDelay> {
     i2c: I2C,
     addr: u8,
+    max_transfer: usize,
+    retry: RetryPolicy,
+    delay: DELAY,
+    data_sent: usize,
+}
+
+impl<I2C, DELAY> I2cInterface<I2C, DELAY> {
+    /// Default largest number of data bytes sent in a single I2C write, one display row
+    pub const DEFAULT_MAX_TRANSFER: usize = 128;
 }
 
 impl<I2C> I2cInterface<I2C>
@@ -17,15 +90,65 @@ where
 {
     /// Create new ssd1306 I2C interface
     pub fn new(i2c: I2C, addr: u8) -> Self {
-        Self { i2c, addr }
+        Self {
+            i2c,
+            addr,
+            max_transfer: Self::DEFAULT_MAX_TRANSFER,
+            retry: RetryPolicy::NONE,
+            delay: NoDelay,
+            data_sent: 0,
+        }
+    }
+}
+
+impl<I2C, DELAY> I2cInterface<I2C, DELAY>
+where
+    I2C: I2c,
+    DELAY: DelayNs,
+{
+    /// Set the largest number of data bytes sent in a single I2C write, not counting the control
+    /// byte. Use a smaller value for controllers with small FIFOs, or a larger one to send a full
+    /// frame in one transaction. A value of 0 is treated as 1.
+    pub fn with_max_transfer(self, max_transfer: usize) -> Self {
+        Self {
+            max_transfer: max_transfer.max(1),
+            ..self
+        }
+    }
+
+    /// Retry failed transfers according to `policy`, waiting between attempts with `delay`
+    pub fn with_retry<D>(self, policy: RetryPolicy, delay: D) -> I2cInterface<I2C, D>
+    where
+        D: DelayNs,
+    {
+        I2cInterface {
+            i2c: self.i2c,
+            addr: self.addr,
+            max_transfer: self.max_transfer,
+            retry: policy,
+            delay,
+            data_sent: 0,
+        }
+    }
+
+    /// Write a control byte followed by `bytes` as a single I2C write. Adjacent writes in a
+    /// transaction go out without a restart, so the caller's buffer doesn't need to be copied.
+    fn write(&mut self, control: u8, bytes: &[u8]) -> Result<(), Error> {
+        self.i2c
+            .transaction(
+                self.addr,
+                &mut [Operation::Write(&[control]), Operation::Write(bytes)],
+            )
+            .map_err(|error| Error::I2c(error.kind()))
     }
 }
 
-impl<I2C> DisplayInterface for I2cInterface<I2C>
+impl<I2C, DELAY> DisplayInterface for I2cInterface<I2C, DELAY>
 // where
 //     I2C: hal::blocking::i2c::Write<Error = CommE>,
-where 
+where
     I2C: I2c + ErrorType,
+    DELAY: DelayNs,
 {
     type Error = Error;
 
@@ -34,63 +157,51 @@ where
     }
 
     fn send_commands(&mut self, cmds: &[u8]) -> Result<(), Self::Error> {
-        // Copy over given commands to new aray to prefix with command identifier
-        let mut writebuf: [u8; 8] = [0; 8];
-        writebuf[1..=cmds.len()].copy_from_slice(&cmds);
-        if (cmds.len() == 1) {
+        if cmds.len() == 1 {
             log::debug!("send_command : length = {} {:#04x}", cmds.len(), cmds[0]);
-        } else if (cmds.len() > 1) {
+        } else if cmds.len() > 1 {
             log::debug!("send_command : length = {} {:#04x} {:#04x}", cmds.len(), cmds[0], cmds[1]);
         }
-        
-        //log::debug!("send_commands, length = {}, command id = {:#04x}", cmds.len(), cmds[0]);
-        Ok(self.i2c
-            .write(self.addr, &writebuf[..=cmds.len()])?)
-            //.map_err(Error::Comm)
+
+        // Prefix the commands with the command control byte
+        self.write(0x00, cmds)
     }
 
     fn send_data(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
-        // Display is always 128px wide
-        const CHUNKLEN: usize = 128;
-
-        const BUFLEN: usize = CHUNKLEN + 1;
+        self.data_sent = 0;
 
         // Noop if the data buffer is empty
         if buf.is_empty() {
             return Ok(());
         }
 
-        let mut page = Page::Page0 as u8;
-
-        // Display width plus 4 start bytes
-        let mut writebuf: [u8; BUFLEN] = [0; BUFLEN];
-
-        writebuf[0] = 0x40; // Following bytes are data bytes
         log::debug!("send_data buf length is {}", buf.len());
-        for chunk in buf.chunks(CHUNKLEN) {
-            // Copy over all data from buffer, leaving the data command byte intact
-            writebuf[1..BUFLEN].copy_from_slice(&chunk);
-
-            self.i2c
-                .write(
-                    self.addr,
-                    &[
-                        0x00, // Command
-                        page, // Page address
-                        0x02, // Lower column address
-                        0x10, // Upper column address (always zero, base is 10h)
-                    ],
-                )?;
-                //.map_err(Error::Comm)?;
-
-            self.i2c.write(self.addr, &writebuf)?; // .map_err(Error::Comm)?;
-
-            page += 1;
+        // The display advances its RAM pointer after every byte, so chunks may end anywhere
+        for chunk in buf.chunks(self.max_transfer) {
+            // Following bytes are data bytes, sent straight from the caller's buffer
+            self.write(0x40, chunk)?;
+            self.data_sent += chunk.len();
         }
 
         Ok(())
     }
-    
-    
 
+    fn data_sent(&self) -> Option<usize> {
+        Some(self.data_sent)
+    }
+
+    fn retry(&mut self, error: &Error, attempt: u8) -> bool {
+        let Error::I2c(kind) = *error else {
+            return false;
+        };
+        if attempt >= self.retry.max_attempts || !(self.retry.retry_on)(kind) {
+            return false;
+        }
+
+        log::debug!("i2c write failed ({:?}), attempt {}", kind, attempt);
+        if self.retry.delay_us > 0 {
+            self.delay.delay_us(self.retry.delay_us);
+        }
+        true
+    }
 }
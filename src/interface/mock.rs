@@ -0,0 +1,214 @@
+//! Simulated SSD1306 for tests
+//!
+//! [`MockInterface`] parses the command stream and writes data into a simulated display RAM,
+//! following the addressing modes of the SSD1306. Transfers can be made to fail after a number of
+//! further bytes, with the bytes before the failure still reaching the display, like a partly
+//! acknowledged I2C write.
+
+extern crate std;
+
+use std::{cell::RefCell, rc::Rc, vec::Vec};
+
+use super::DisplayInterface;
+
+/// State of the simulated display, shared between the interface and the test
+pub struct MockState {
+    /// Display RAM, 8 pages of 128 columns
+    pub ram: [[u8; 128]; 8],
+    /// Memory addressing mode, 0 horizontal, 1 vertical, 2 page
+    pub mode: u8,
+    /// Contrast register
+    pub contrast: u8,
+    /// Display RAM row shown on the top row of the panel
+    pub start_line: u8,
+    /// Column and page window for horizontal and vertical mode
+    pub window: ((u8, u8), (u8, u8)),
+    /// Current RAM column and page
+    pub position: (u8, u8),
+    /// Every completed command with its arguments
+    pub commands: Vec<Vec<u8>>,
+    /// Total number of data bytes that reached the display RAM
+    pub data_bytes: usize,
+    /// Fail the command transfer that would send more than this many further command bytes
+    pub fail_commands_after: Option<usize>,
+    /// Fail the data transfer that would send more than this many further data bytes
+    pub fail_data_after: Option<usize>,
+    /// Number of attempts the interface allows per transfer
+    pub max_attempts: u8,
+    /// Data is sent in chunks of this size, `data_sent` counts complete chunks only
+    pub chunk: usize,
+    pending: Vec<u8>,
+    data_sent: usize,
+}
+
+impl MockState {
+    fn new() -> Self {
+        MockState {
+            ram: [[0; 128]; 8],
+            mode: 2,
+            contrast: 0x7F,
+            start_line: 0,
+            window: ((0, 127), (0, 7)),
+            position: (0, 0),
+            commands: Vec::new(),
+            data_bytes: 0,
+            fail_commands_after: None,
+            fail_data_after: None,
+            max_attempts: 1,
+            chunk: 32,
+            pending: Vec::new(),
+            data_sent: 0,
+        }
+    }
+
+    /// Number of argument bytes following a command byte
+    fn argument_count(command: u8) -> usize {
+        match command {
+            0x81 | 0x20 | 0xA8 | 0xD3 | 0xD5 | 0xD9 | 0xDA | 0xDB | 0x8D | 0xD6 => 1,
+            0x21 | 0x22 | 0xA3 => 2,
+            0x29 | 0x2A => 5,
+            0x26 | 0x27 => 6,
+            _ => 0,
+        }
+    }
+
+    fn command_byte(&mut self, byte: u8) {
+        self.pending.push(byte);
+        if self.pending.len() <= Self::argument_count(self.pending[0]) {
+            return;
+        }
+
+        let command = core::mem::take(&mut self.pending);
+        match command[..] {
+            [0x81, contrast] => self.contrast = contrast,
+            [0x20, mode] => self.mode = mode & 0x3,
+            [0x21, start, end] => {
+                self.window.0 = (start, end);
+                self.position.0 = start;
+            }
+            [0x22, start, end] => {
+                self.window.1 = (start, end);
+                self.position.1 = start;
+            }
+            [line @ 0x40..=0x7F] => self.start_line = line & 0x3F,
+            [page @ 0xB0..=0xB7] => self.position.1 = page & 0x7,
+            [low @ 0x00..=0x0F] => self.position.0 = (self.position.0 & 0xF0) | low,
+            [high @ 0x10..=0x1F] => self.position.0 = (self.position.0 & 0x0F) | (high & 0xF) << 4,
+            _ => {}
+        }
+        self.commands.push(command);
+    }
+
+    fn data_byte(&mut self, byte: u8) {
+        let (column, page) = self.position;
+        if column < 128 && page < 8 {
+            self.ram[page as usize][column as usize] = byte;
+        }
+        self.data_bytes += 1;
+
+        let ((first_column, last_column), (first_page, last_page)) = self.window;
+        let (mut column, mut page) = self.position;
+        match self.mode {
+            0 => {
+                column += 1;
+                if column > last_column {
+                    column = first_column;
+                    page = if page >= last_page {
+                        first_page
+                    } else {
+                        page + 1
+                    };
+                }
+            }
+            1 => {
+                page += 1;
+                if page > last_page {
+                    page = first_page;
+                    column = if column >= last_column {
+                        first_column
+                    } else {
+                        column + 1
+                    };
+                }
+            }
+            _ => column = column.saturating_add(1),
+        }
+        self.position = (column, page);
+    }
+}
+
+/// Shared handle to the state of the simulated display
+pub type MockHandle = Rc<RefCell<MockState>>;
+
+/// Interface to a simulated display
+pub struct MockInterface {
+    state: MockHandle,
+}
+
+impl MockInterface {
+    /// Create an interface and a handle to the state of the simulated display
+    pub fn new() -> (Self, MockHandle) {
+        let state = Rc::new(RefCell::new(MockState::new()));
+
+        (
+            MockInterface {
+                state: state.clone(),
+            },
+            state,
+        )
+    }
+}
+
+impl DisplayInterface for MockInterface {
+    type Error = ();
+
+    fn init(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn send_commands(&mut self, cmds: &[u8]) -> Result<(), ()> {
+        let mut state = self.state.borrow_mut();
+        for &byte in cmds {
+            match state.fail_commands_after {
+                Some(0) => {
+                    state.fail_commands_after = None;
+                    return Err(());
+                }
+                Some(left) => state.fail_commands_after = Some(left - 1),
+                None => {}
+            }
+            state.command_byte(byte);
+        }
+        Ok(())
+    }
+
+    fn send_data(&mut self, buf: &[u8]) -> Result<(), ()> {
+        let mut state = self.state.borrow_mut();
+        state.data_sent = 0;
+        let chunk = state.chunk;
+        for (i, &byte) in buf.iter().enumerate() {
+            match state.fail_data_after {
+                Some(0) => {
+                    state.fail_data_after = None;
+                    return Err(());
+                }
+                Some(left) => state.fail_data_after = Some(left - 1),
+                None => {}
+            }
+            state.data_byte(byte);
+            if (i + 1) % chunk == 0 {
+                state.data_sent = i + 1;
+            }
+        }
+        state.data_sent = buf.len();
+        Ok(())
+    }
+
+    fn data_sent(&self) -> Option<usize> {
+        Some(self.state.borrow().data_sent)
+    }
+
+    fn retry(&mut self, _error: &(), attempt: u8) -> bool {
+        attempt < self.state.borrow().max_attempts
+    }
+}
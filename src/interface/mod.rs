@@ -2,6 +2,8 @@
 //!
 
 pub mod i2c;
+#[cfg(test)]
+pub(crate) mod mock;
 
 
 
@@ -12,10 +14,21 @@ pub trait DisplayInterface {
 
     /// Initialize device.
     fn init(&mut self) -> Result<(), Self::Error>;
-    /// Send a batch of up to 8 commands to display.
+    /// Send a batch of commands to display.
     fn send_commands(&mut self, cmd: &[u8]) -> Result<(), Self::Error>;
     /// Send data to display.
     fn send_data(&mut self, buf: &[u8]) -> Result<(), Self::Error>;
+    /// Number of bytes of the last `send_data` call that reached the display, if known. Used to
+    /// resume an interrupted transfer.
+    fn data_sent(&self) -> Option<usize> {
+        None
+    }
+    /// Whether a transfer that failed with `error` should be attempted again, `attempt` being the
+    /// number of attempts made so far. Waits between attempts if configured to. The caller
+    /// restores the display state before sending again, a transfer is never repeated as is.
+    fn retry(&mut self, _error: &Self::Error, _attempt: u8) -> bool {
+        false
+    }
 }
 
-pub use self::i2c::I2cInterface;
+pub use self::i2c::{I2cInterface, NoDelay, RetryPolicy};
@@ -43,17 +43,28 @@ where
 
 extern crate embedded_hal as hal;
 
+pub mod brightness;
 pub mod builder;
+pub mod burnin;
+#[cfg(feature = "graphics")]
+pub mod canvas;
 mod command;
+#[cfg(feature = "graphics")]
+pub mod dither;
 pub mod displayrotation;
 pub mod displaysize;
 pub mod interface;
+pub mod logview;
 pub mod mode;
 pub mod prelude;
 pub mod properties;
-#[doc(hidden)]
+pub mod rasterop;
+#[cfg(feature = "graphics")]
+pub mod tiled;
+#[cfg(feature = "graphics")]
+pub mod viewport;
+//#[doc(hidden)]
 //pub mod test_helpers;
 
-mod brightness;
-
 pub use crate::builder::Builder;
+pub use crate::command::AddrMode;
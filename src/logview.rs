@@ -0,0 +1,201 @@
+//! Scrolling text log view
+//!
+//! A [`LogView`] uses the 64 rows of display RAM as a ring buffer of 8 pixel high lines. Each new
+//! line is drawn into the page just below the visible area, only that page is sent to the display
+//! and the display start line is moved down by one page. Older lines scroll up without being
+//! redrawn or resent.
+//!
+//! The view works in display RAM coordinates and only supports `Rotate0` and `Rotate180`.
+//!
+//! ```rust,ignore
+//! use ssd1306_i2c::logview::LogView;
+//!
+//! let mut log = LogView::new(&mut display);
+//! log.push_line(|line| {
+//!     Text::with_baseline("sensor ok", Point::zero(), style, Baseline::Top)
+//!         .draw(line)
+//!         .unwrap();
+//! })
+//! .unwrap();
+//! ```
+
+use crate::{interface::DisplayInterface, mode::GraphicsMode};
+
+/// Number of pages of display RAM
+const RAM_PAGES: u8 = 8;
+
+/// Scrolling log view on top of a [`GraphicsMode`]
+pub struct LogView<'a, DI>
+where
+    DI: DisplayInterface,
+{
+    display: &'a mut GraphicsMode<DI>,
+}
+
+impl<'a, DI> LogView<'a, DI>
+where
+    DI: DisplayInterface,
+{
+    /// Create a log view on the display. The current start line and buffer contents are kept.
+    pub fn new(display: &'a mut GraphicsMode<DI>) -> Self {
+        LogView { display }
+    }
+
+    /// Append a line at the bottom of the display. `draw` renders the line into a target 8
+    /// pixels high and as wide as the display, which is then sent to the display before
+    /// scrolling the older lines up by one line.
+    pub fn push_line<F>(&mut self, draw: F) -> Result<(), DI::Error>
+    where
+        F: FnOnce(&mut LogLine<'_, DI>),
+    {
+        let (_, display_height) = self.display.get_size().dimensions();
+        let visible_pages = display_height / 8;
+        let top_page = self.display.get_start_line() / 8;
+        let page = (top_page + visible_pages) % RAM_PAGES;
+
+        let mut line = LogLine {
+            display: &mut *self.display,
+            page,
+        };
+        line.clear();
+        draw(&mut line);
+
+        self.display.flush_page(page)?;
+        self.display
+            .set_start_line((top_page + 1) % RAM_PAGES * 8)
+    }
+
+    /// Clear all lines and reset the start line
+    pub fn clear(&mut self) -> Result<(), DI::Error> {
+        self.display.clear();
+        self.display.set_start_line(0)?;
+        for page in 0..RAM_PAGES {
+            self.display.flush_page(page)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single line of a [`LogView`], one page of display RAM
+pub struct LogLine<'a, DI>
+where
+    DI: DisplayInterface,
+{
+    display: &'a mut GraphicsMode<DI>,
+    page: u8,
+}
+
+impl<DI> LogLine<'_, DI>
+where
+    DI: DisplayInterface,
+{
+    /// Turn a pixel of the line on or off. `y` must be below 8, pixels outside the line are
+    /// ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: bool) {
+        if y < 8 {
+            self.display
+                .set_raw_pixel(x, self.page as u32 * 8 + y, value);
+        }
+    }
+
+    /// Clear the line
+    pub fn clear(&mut self) {
+        if let Some(page) = self.display.page_mut(self.page) {
+            page.fill(0);
+        }
+    }
+}
+
+#[cfg(feature = "graphics")]
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Size},
+    pixelcolor::BinaryColor,
+    Pixel,
+};
+
+#[cfg(feature = "graphics")]
+impl<DI> DrawTarget for LogLine<'_, DI>
+where
+    DI: DisplayInterface,
+{
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bb = self.bounding_box();
+
+        pixels
+            .into_iter()
+            .filter(|Pixel(pos, _color)| bb.contains(*pos))
+            .for_each(|Pixel(pos, color)| self.set_pixel(pos.x as u32, pos.y as u32, color.is_on()));
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<DI> OriginDimensions for LogLine<'_, DI>
+where
+    DI: DisplayInterface,
+{
+    fn size(&self) -> Size {
+        let (w, _) = self.display.get_size().dimensions();
+
+        Size::new(w.into(), 8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        command::AddrMode, displayrotation::DisplayRotation, displaysize::DisplaySize,
+        interface::mock::MockInterface, mode::displaymode::DisplayModeTrait,
+        properties::DisplayProperties,
+    };
+
+    #[test]
+    fn push_line_sends_one_page_and_scrolls() {
+        for (size, visible_pages) in [
+            (DisplaySize::Display128x64NoOffset, 8),
+            (DisplaySize::Display128x32, 4),
+        ] {
+            let (iface, state) = MockInterface::new();
+            let mut display = GraphicsMode::new(DisplayProperties::new(
+                iface,
+                size,
+                DisplayRotation::Rotate0,
+                AddrMode::Horizontal,
+            ));
+            display.init().unwrap();
+
+            let mut log = LogView::new(&mut display);
+            log.clear().unwrap();
+            for line in 0..10u32 {
+                let before = state.borrow().data_bytes;
+                log.push_line(|target| target.set_pixel(line, line % 8, true))
+                    .unwrap();
+
+                let state = state.borrow();
+                assert_eq!(state.data_bytes - before, 128, "line {line}");
+                // The line went into the page below the previously visible ones, which is now
+                // the last visible page
+                let page = (line as usize + visible_pages) % 8;
+                assert_eq!(state.start_line, (line as u8 + 1) % 8 * 8, "line {line}");
+
+                for (x, &byte) in state.ram[page].iter().enumerate() {
+                    let expected = if x == line as usize {
+                        1 << (line % 8)
+                    } else {
+                        0
+                    };
+                    assert_eq!(byte, expected, "line {line} column {x}");
+                }
+            }
+        }
+    }
+}
@@ -8,6 +8,17 @@
 //!     primitives::{Circle, Line, PrimitiveStyle, Rectangle},
 //! };
 //! use ssd1306_i2c::{prelude::*, Builder};
+//! # use embedded_hal::i2c::{ErrorType, I2c, Operation};
+//! # struct I2cStub;
+//! # impl ErrorType for I2cStub {
+//! #     type Error = core::convert::Infallible;
+//! # }
+//! # impl I2c for I2cStub {
+//! #     fn transaction(&mut self, _: u8, _: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+//! #         Ok(())
+//! #     }
+//! # }
+//! # let i2c = I2cStub;
 //!
 //! let mut display: GraphicsMode<_> = Builder::new().connect_i2c(i2c).into();
 //!
@@ -41,16 +52,156 @@
 //!
 //! display.flush().unwrap();
 //! ```
+//!
+//! # Buffer layout
+//!
+//! The frame buffer returned by [`GraphicsMode::buffer`] uses the native layout of the display
+//! RAM. It is split into pages of 8 pixel rows, with one byte per column. Pages are stored one
+//! after the other, each [`GraphicsMode::page_len`] bytes long (the panel width). In each byte,
+//! bit 0 is the top row of the page and bit 7 the bottom row.
+//!
+//! The layout is always in panel coordinates, rotation is applied when indexing:
+//!
+//! - `Rotate0` and `Rotate180`: pixel (x, y) is in byte `y / 8 * page_len + x`, bit `y % 8`.
+//!   Rotate180 is done by the display hardware.
+//! - `Rotate90` and `Rotate270`: the buffer is stored column by column instead, in the order
+//!   the display expects with vertical addressing. Each panel column takes `pages` bytes, where
+//!   `pages` is the panel height / 8, so pixel (x, y) is in byte `y * pages + x / 8`, bit
+//!   `x % 8`. Logical rows are contiguous and the frame goes out without transposing.
+//!
+//! [`GraphicsMode::pixel_location`] computes this for the current rotation.
 
 //use hal::{blocking::delay::DelayMs, digital::v2::OutputPin};
+use core::task::Poll;
+
 use embedded_hal::delay::DelayNs;
 
 use crate::{
-    displayrotation::DisplayRotation, interface::DisplayInterface,
-    mode::displaymode::DisplayModeTrait, properties::DisplayProperties, Error,
+    brightness::Brightness,
+    burnin::{BurnInConfig, BurnInState, Screensaver},
+    command::AddrMode,
+    displayrotation::DisplayRotation,
+    displaysize::DisplaySize,
+    interface::DisplayInterface,
+    mode::displaymode::DisplayModeTrait,
+    properties::DisplayProperties,
+    rasterop::RasterOp,
+    Error,
 };
 
-const BUFFER_SIZE: usize = 132 * 64 / 8;
+pub(crate) const BUFFER_SIZE: usize = 132 * 64 / 8;
+
+/// Locate a pixel in a page-packed frame buffer, returning the byte index and bit mask, or `None`
+/// if the coordinates are out of bounds
+pub(crate) fn buffer_position(
+    display_size: DisplaySize,
+    display_rotation: DisplayRotation,
+    x: u32,
+    y: u32,
+) -> Option<(usize, u8)> {
+    let (display_width, _) = display_size.dimensions();
+
+    let (column, row) = match display_rotation {
+        DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (x, y),
+        DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (y, x),
+    };
+    if column >= display_width as u32 {
+        return None;
+    }
+
+    let idx = ((row as usize) / 8 * display_width as usize) + (column as usize);
+    if idx >= BUFFER_SIZE {
+        return None;
+    }
+
+    Some((idx, 1 << (row % 8)))
+}
+
+/// Whether a [`GraphicsMode`] buffer is stored column by column for the given rotation
+fn is_column_major(display_rotation: DisplayRotation) -> bool {
+    matches!(
+        display_rotation,
+        DisplayRotation::Rotate90 | DisplayRotation::Rotate270
+    )
+}
+
+/// Locate a pixel by display RAM column and row in a [`GraphicsMode`] buffer, which is either
+/// page-major or column-major
+fn layout_position(
+    display_size: DisplaySize,
+    column_major: bool,
+    column: u32,
+    row: u32,
+) -> Option<(usize, u8)> {
+    if !column_major {
+        return buffer_position(display_size, DisplayRotation::Rotate0, column, row);
+    }
+
+    let (display_width, display_height) = display_size.dimensions();
+    if column >= display_width as u32 || row >= display_height as u32 {
+        return None;
+    }
+
+    let pages = display_height as usize / 8;
+    Some((column as usize * pages + row as usize / 8, 1 << (row % 8)))
+}
+
+/// Pages of display RAM changed since they were last sent, tracked for every column
+#[derive(Clone, Copy)]
+struct DirtyColumns {
+    /// Bit mask of the changed pages of each column
+    columns: [u8; 132],
+}
+
+impl DirtyColumns {
+    /// Every column of every page changed
+    const ALL: Self = DirtyColumns {
+        columns: [u8::MAX; 132],
+    };
+
+    /// Nothing changed
+    const NONE: Self = DirtyColumns { columns: [0; 132] };
+
+    /// Mark `column` of `page` as changed
+    fn mark(&mut self, page: usize, column: usize) {
+        self.columns[column] |= 1 << page;
+    }
+
+    /// Mark every column of `page` as changed
+    fn mark_page(&mut self, page: usize) {
+        for pages in self.columns.iter_mut() {
+            *pages |= 1 << page;
+        }
+    }
+
+    /// Mark every column of `page` as sent
+    fn clear_page(&mut self, page: usize) {
+        for pages in self.columns.iter_mut() {
+            *pages &= !(1 << page);
+        }
+    }
+
+    /// Mark `columns` of `page` as sent
+    #[cfg(feature = "graphics")]
+    fn clear(&mut self, page: usize, columns: &Range<u32>) {
+        for pages in &mut self.columns[columns.start as usize..columns.end as usize] {
+            *pages &= !(1 << page);
+        }
+    }
+
+    /// Bit mask of the pages with changed columns
+    fn pages(&self) -> u8 {
+        self.columns.iter().fold(0, |mask, pages| mask | pages)
+    }
+
+    /// Whether any of `columns` of `page` changed
+    #[cfg(feature = "graphics")]
+    fn intersects(&self, page: usize, columns: &Range<u32>) -> bool {
+        self.columns[columns.start as usize..columns.end as usize]
+            .iter()
+            .any(|pages| pages & (1 << page) != 0)
+    }
+}
 
 /// Graphics mode handler
 pub struct GraphicsMode<DI>
@@ -59,6 +210,13 @@ where
 {
     properties: DisplayProperties<DI>,
     buffer: [u8; BUFFER_SIZE],
+    burn_in: Option<BurnInState>,
+    pending_page: Option<usize>,
+    watchdog: Option<u8>,
+    bus_errors: u8,
+    raster_op: RasterOp,
+    dirty: DirtyColumns,
+    flush_queue: u8,
 }
 
 impl<DI> DisplayModeTrait<DI> for GraphicsMode<DI>
@@ -70,6 +228,13 @@ where
         GraphicsMode {
             properties,
             buffer: [0; BUFFER_SIZE],
+            burn_in: None,
+            pending_page: None,
+            watchdog: None,
+            bus_errors: 0,
+            raster_op: RasterOp::Copy,
+            dirty: DirtyColumns::ALL,
+            flush_queue: 0,
         }
     }
 
@@ -86,6 +251,8 @@ where
     /// Clear the display buffer. You need to call `display.flush()` for any effect on the screen
     pub fn clear(&mut self) {
         self.buffer = [0; BUFFER_SIZE];
+        self.dirty = DirtyColumns::ALL;
+        self.mark_changed();
     }
 
     /// Reset display
@@ -106,103 +273,1021 @@ where
 
     /// Write out data to display
     pub fn flush(&mut self) -> Result<(), DI::Error> {
+        if let Some((dimmed, screensaver)) =
+            self.burn_in.as_mut().and_then(BurnInState::take_changed)
+        {
+            self.wake(dimmed, screensaver)?;
+        }
+
+        let result = self.send_buffer_from(0);
+        self.check_watchdog(result)
+    }
+
+    /// Re-initialise the display, replaying all runtime settings (rotation, brightness, invert,
+    /// zoom, ...), and send the whole buffer again. Use this after the display lost power.
+    pub fn reinit(&mut self) -> Result<(), DI::Error> {
+        self.properties.init_column_mode()?;
+        if let Some(state) = self.burn_in.filter(BurnInState::is_dimmed) {
+            self.properties
+                .send_brightness(state.config().dim_brightness())?;
+        }
+        self.send_buffer_from(0)
+    }
+
+    /// Automatically call [`reinit`](Self::reinit) from `flush` after `errors` consecutive
+    /// failed flushes, e.g. when the display was hot-plugged or browned out. `None` disables the
+    /// watchdog, which is the default.
+    pub fn set_watchdog(&mut self, errors: Option<u8>) {
+        self.watchdog = errors;
+        self.bus_errors = 0;
+    }
+
+    /// Count consecutive flush failures and re-initialise the display once the watchdog limit is
+    /// reached
+    fn check_watchdog(&mut self, result: Result<(), DI::Error>) -> Result<(), DI::Error> {
+        if result.is_ok() {
+            self.bus_errors = 0;
+            return result;
+        }
+
+        self.bus_errors = self.bus_errors.saturating_add(1);
+        match self.watchdog {
+            Some(limit) if self.bus_errors >= limit => {
+                log::debug!("{} consecutive flush errors, re-initialising", self.bus_errors);
+                self.bus_errors = 0;
+                self.reinit()
+            }
+            _ => result,
+        }
+    }
+
+    /// Whether the last `flush` or `resume_flush` failed before the whole frame was sent
+    pub fn flush_pending(&self) -> bool {
+        self.pending_page.is_some()
+    }
+
+    /// Continue an interrupted `flush`, starting at the page that failed instead of resending the
+    /// whole frame. Does nothing if the last flush completed.
+    pub fn resume_flush(&mut self) -> Result<(), DI::Error> {
+        let result = match self.pending_page {
+            Some(page) => self.send_buffer_from(page),
+            None => Ok(()),
+        };
+        self.check_watchdog(result)
+    }
+
+    /// Start an incremental flush of the pages changed since the last flush. Call
+    /// [`flush_step`](Self::flush_step) until it returns `Poll::Ready` to send them one page at a
+    /// time, e.g. between other work in a main loop. With burn-in protection enabled all pages
+    /// are sent, as the pixel shift moves the whole image.
+    pub fn flush_begin(&mut self) -> Result<(), DI::Error> {
+        if let Some((dimmed, screensaver)) =
+            self.burn_in.as_mut().and_then(BurnInState::take_changed)
+        {
+            self.wake(dimmed, screensaver)?;
+        }
+
+        self.flush_queue |= if self.burn_in.is_some() {
+            u8::MAX
+        } else {
+            self.dirty.pages()
+        };
+        self.dirty = DirtyColumns::NONE;
+        Ok(())
+    }
+
+    /// Send the next page queued by [`flush_begin`](Self::flush_begin). Returns `Poll::Pending`
+    /// while more pages are left and `Poll::Ready` once all are sent. A page that fails to send
+    /// stays queued and is retried by the next call.
+    pub fn flush_step(&mut self) -> Poll<Result<(), DI::Error>> {
+        let page_count = self.page_count();
+        let Some(page) = (0..page_count).find(|page| self.flush_queue & (1 << page) != 0) else {
+            self.flush_queue = 0;
+            return Poll::Ready(Ok(()));
+        };
+
+        let length = self.active_len();
+        let column_major = self.column_major();
+        let result = Self::send_frame(
+            &mut self.properties,
+            self.burn_in.as_ref(),
+            &self.buffer[..length],
+            column_major,
+            page,
+            page + 1,
+        );
+        if result.is_err() {
+            return Poll::Ready(self.check_watchdog(result));
+        }
+        self.bus_errors = 0;
+
+        self.flush_queue &= !(1 << page);
+        if (page + 1..page_count).any(|page| self.flush_queue & (1 << page) != 0) {
+            Poll::Pending
+        } else {
+            self.flush_queue = 0;
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// Send the internal buffer starting at `first_page`, remembering where it failed if the
+    /// transfer is interrupted
+    fn send_buffer_from(&mut self, first_page: usize) -> Result<(), DI::Error> {
+        let length = self.active_len();
+        let column_major = self.column_major();
+        let end_page = self.page_count();
+        let result = Self::send_frame(
+            &mut self.properties,
+            self.burn_in.as_ref(),
+            &self.buffer[..length],
+            column_major,
+            first_page,
+            end_page,
+        );
+
+        if result.is_ok() {
+            // The whole frame is on the display
+            self.dirty = DirtyColumns::NONE;
+            self.flush_queue = 0;
+        }
+
+        self.pending_page = match result {
+            Ok(()) => None,
+            // Data goes out column by column in vertical mode, there is no complete page to
+            // resume after
+            Err(_) if self.properties.get_addressing_mode() == AddrMode::Vertical => Some(0),
+            Err(_) => Some(first_page + self.properties.draw_offset() / self.page_len()),
+        };
+        result
+    }
+
+    /// Write out a caller-owned frame to the display without copying it into the internal buffer.
+    /// The frame uses the same layout as [`buffer`](Self::buffer) and must have the same length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame` is not exactly `buffer().len()` bytes long.
+    pub fn flush_from(&mut self, frame: &[u8]) -> Result<(), DI::Error> {
+        assert_eq!(
+            frame.len(),
+            self.active_len(),
+            "frame length does not match the display size"
+        );
+
+        // The display no longer shows the internal buffer, the next incremental flush has to
+        // send all of it
+        self.dirty = DirtyColumns::ALL;
+        self.mark_changed();
+        if let Some((dimmed, screensaver)) =
+            self.burn_in.as_mut().and_then(BurnInState::take_changed)
+        {
+            self.wake(dimmed, screensaver)?;
+        }
+
+        let column_major = self.column_major();
+        let end_page = self.page_count();
+        Self::send_frame(
+            &mut self.properties,
+            self.burn_in.as_ref(),
+            frame,
+            column_major,
+            0,
+            end_page,
+        )
+    }
+
+    /// Number of buffer bytes used by the configured display size
+    fn active_len(&self) -> usize {
+        let (display_width, display_height) = self.properties.get_size().dimensions();
+
+        (display_width as usize) * (display_height as usize) / 8
+    }
+
+    /// Number of pages (8 pixel rows) of display RAM used by the configured display size
+    fn page_count(&self) -> usize {
+        self.properties.get_size().dimensions().1 as usize / 8
+    }
+
+    /// Whether the buffer is stored column by column, see the [module level documentation](self)
+    fn column_major(&self) -> bool {
+        is_column_major(self.properties.get_rotation())
+    }
+
+    /// Send the buffer to other displays showing the same content, e.g. a front and a rear
+    /// panel. Each display is described by its own properties, including rotation, size, display
+    /// offset and brightness, and must have been initialised with `init_column_mode`.
+    ///
+    /// Pixels keep their X and Y coordinates, content outside a smaller display is dropped.
+    /// Displays whose size and buffer layout match this one are sent the buffer as is. For all
+    /// others the buffer is converted once per distinct geometry, so consecutive displays with
+    /// the same geometry share the converted frame.
+    pub fn flush_mirrors<D>(&self, mirrors: &mut [DisplayProperties<D>]) -> Result<(), D::Error>
+    where
+        D: DisplayInterface,
+    {
         let display_size = self.properties.get_size();
+        let column_major = self.column_major();
+        let (width, height) = self.get_dimensions();
+
+        let mut frame = [0u8; BUFFER_SIZE];
+        // Geometry of the frame converted last
+        let mut converted = None;
+
+        for mirror in mirrors.iter_mut() {
+            let size = mirror.get_size();
+            let mirror_column_major = is_column_major(mirror.get_rotation());
+            let (display_width, display_height) = size.dimensions();
+            let length = display_width as usize * display_height as usize / 8;
+
+            if size.dimensions() == display_size.dimensions() && mirror_column_major == column_major
+            {
+                Self::send_frame(
+                    mirror,
+                    None,
+                    self.buffer(),
+                    column_major,
+                    0,
+                    display_height as usize / 8,
+                )?;
+                continue;
+            }
+
+            let geometry = (size.dimensions(), mirror_column_major);
+            if converted != Some(geometry) {
+                frame = [0; BUFFER_SIZE];
+                let (mirror_width, mirror_height) = mirror.get_dimensions();
+                for y in 0..height.min(mirror_height) as u32 {
+                    for x in 0..width.min(mirror_width) as u32 {
+                        let on = self
+                            .pixel_location(x, y)
+                            .is_some_and(|(idx, bit)| self.buffer[idx] & bit != 0);
+                        if !on {
+                            continue;
+                        }
+
+                        let (column, row) = if mirror_column_major { (y, x) } else { (x, y) };
+                        if let Some((idx, bit)) =
+                            layout_position(size, mirror_column_major, column, row)
+                        {
+                            frame[idx] |= bit;
+                        }
+                    }
+                }
+                converted = Some(geometry);
+            }
+
+            Self::send_frame(
+                mirror,
+                None,
+                &frame[..length],
+                mirror_column_major,
+                0,
+                display_height as usize / 8,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Send the pages from `first_page` up to `end_page` of a full frame, applying the burn-in
+    /// protection pixel shift or screensaver if active. `column_major` selects the layout of
+    /// `frame`.
+    fn send_frame<D>(
+        properties: &mut DisplayProperties<D>,
+        burn_in: Option<&BurnInState>,
+        frame: &[u8],
+        column_major: bool,
+        first_page: usize,
+        end_page: usize,
+    ) -> Result<(), D::Error>
+    where
+        D: DisplayInterface,
+    {
+        let display_size = properties.get_size();
 
         // Ensure the display buffer is at the origin of the display before we send the full frame
         // to prevent accidental offsets
         let (display_width, display_height) = display_size.dimensions();
         let column_offset = display_size.column_offset();
-        self.properties.set_draw_area(
-            (column_offset, 0),
-            (display_width + column_offset, display_height),
+        properties.set_draw_area(
+            (column_offset, first_page as u8 * 8),
+            (display_width + column_offset, end_page as u8 * 8),
         )?;
 
-        let length = (display_width as usize) * (display_height as usize) / 8;
+        let width = display_width as usize;
+        let pages = display_height as usize / 8;
 
-        self.properties.draw(&self.buffer[..length])
-    }
+        let ((shift_x, shift_y), step, dots) = match burn_in {
+            Some(state) => (
+                state.offset(),
+                state.step() as usize,
+                state.is_screensaver() && state.config().screensaver() == Screensaver::Dots,
+            ),
+            None => ((0, 0), 0, false),
+        };
+        let (shift_x, shift_y) = (shift_x as isize, shift_y as isize);
 
-    /// Turn a pixel on or off. A non-zero `value` is treated as on, `0` as off. If the X and Y
-    /// coordinates are out of the bounds of the display, this method call is a noop.
-    pub fn set_pixel(&mut self, x: u32, y: u32, value: u8) {
-        let (display_width, _) = self.properties.get_size().dimensions();
-        let display_rotation = self.properties.get_rotation();
+        // Byte of the unshifted frame, blank outside the visible area
+        let source = |page: isize, x: isize| -> u8 {
+            if !(0..width as isize).contains(&x) || !(0..pages as isize).contains(&page) {
+                0
+            } else if column_major {
+                frame[x as usize * pages + page as usize]
+            } else {
+                frame[page as usize * width + x as usize]
+            }
+        };
 
-        let idx = match display_rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                if x >= display_width as u32 {
-                    return;
+        // Byte of the outgoing frame at the given page and column
+        let byte_at = |page: usize, x: usize| -> u8 {
+            if dots {
+                if (x + step * 5).is_multiple_of(16) {
+                    1 << ((x / 16 + page + step) % 8)
+                } else {
+                    0
                 }
-                ((y as usize) / 8 * display_width as usize) + (x as usize)
+            } else {
+                // Software pixel shift for burn-in protection. Rows shifted in from outside the
+                // visible area are blank, like the columns.
+                let (page, src) = (page as isize, x as isize - shift_x);
+                match shift_y {
+                    0 => source(page, src),
+                    1.. => {
+                        let rows = shift_y as u32;
+                        source(page, src) << rows | source(page - 1, src) >> (8 - rows)
+                    }
+                    _ => {
+                        let rows = -shift_y as u32;
+                        source(page, src) >> rows | source(page + 1, src) << (8 - rows)
+                    }
+                }
+            }
+        };
+
+        let plain = !dots && shift_x == 0 && shift_y == 0;
+        if properties.get_addressing_mode() == AddrMode::Vertical {
+            if plain && column_major && first_page == 0 && end_page == pages {
+                // The buffer is already in the order the display expects
+                return properties.draw(frame);
             }
 
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                if y >= display_width as u32 {
-                    return;
+            // The display expects one column at a time, transpose a few columns at a time
+            const COLUMNS: usize = 16;
+            let mut chunk = [0u8; COLUMNS * 8];
+            let page_count = end_page - first_page;
+            for first_column in (0..width).step_by(COLUMNS) {
+                let columns = COLUMNS.min(width - first_column);
+                for column in 0..columns {
+                    for page in 0..page_count {
+                        chunk[column * page_count + page] =
+                            byte_at(first_page + page, first_column + column);
+                    }
                 }
-                ((x as usize) / 8 * display_width as usize) + (y as usize)
+                properties.draw(&chunk[..columns * page_count])?;
             }
+            Ok(())
+        } else if !plain || column_major {
+            // Transform one page at a time
+            let mut row = [0u8; 132];
+            for page in first_page..end_page {
+                for (x, out) in row[..width].iter_mut().enumerate() {
+                    *out = byte_at(page, x);
+                }
+                properties.draw(&row[..width])?;
+            }
+            Ok(())
+        } else {
+            properties.draw(&frame[first_page * width..end_page * width])
+        }
+    }
+
+    /// Get the frame buffer for the configured display size. See the
+    /// [module level documentation](self) for the layout.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer[..self.active_len()]
+    }
+
+    /// Get the frame buffer for the configured display size for direct writing. See the
+    /// [module level documentation](self) for the layout.
+    pub fn buffer_mut(&mut self) -> &mut [u8] {
+        let length = self.active_len();
+        // The content can't be tracked once handed out, assume it changes
+        self.dirty = DirtyColumns::ALL;
+        self.mark_changed();
+
+        &mut self.buffer[..length]
+    }
+
+    /// Get the byte index into [`buffer`](Self::buffer) and the bit mask of the pixel at `x`, `y`,
+    /// taking into account the current rotation of the display. Returns `None` if the coordinates
+    /// are out of bounds.
+    pub fn pixel_location(&self, x: u32, y: u32) -> Option<(usize, u8)> {
+        let (column, row) = if self.column_major() { (y, x) } else { (x, y) };
+
+        self.raw_position(column, row)
+            .filter(|&(idx, _)| idx < self.active_len())
+    }
+
+    /// Locate a pixel by display RAM column and row in the buffer, using the layout of the
+    /// current rotation
+    fn raw_position(&self, column: u32, row: u32) -> Option<(usize, u8)> {
+        layout_position(self.properties.get_size(), self.column_major(), column, row)
+    }
+
+    /// Number of bytes in one page (8 pixel rows) of the buffer, i.e. the panel width
+    pub fn page_len(&self) -> usize {
+        self.properties.get_size().dimensions().0 as usize
+    }
+
+    /// Enable burn-in protection with the given configuration. See the
+    /// [burnin module](../../burnin/index.html) for details. The protection is driven by calling
+    /// `burn_in_tick` periodically.
+    pub fn enable_burn_in_protection(&mut self, config: BurnInConfig) {
+        self.burn_in = Some(BurnInState::new(config));
+    }
+
+    /// Disable burn-in protection, restoring offset, brightness and display state, and redraw the
+    /// buffer contents
+    pub fn disable_burn_in_protection(&mut self) -> Result<(), DI::Error> {
+        if let Some(state) = self.burn_in.take() {
+            self.wake(state.is_dimmed(), state.is_screensaver())?;
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Advance the burn-in protection timers by `elapsed` ticks, in the same unit as the intervals
+    /// of the [`BurnInConfig`]. Shifts the image, dims the display or starts the screensaver when
+    /// due. Does nothing if burn-in protection is not enabled.
+    pub fn burn_in_tick(&mut self, elapsed: u32) -> Result<(), DI::Error> {
+        let Some(state) = self.burn_in.as_mut() else {
+            return Ok(());
         };
+        let previous_shift = state.offset();
+        let actions = state.tick(elapsed);
+        let shift = state.offset();
+        let config = *state.config();
 
-        if idx >= self.buffer.len() {
-            return;
+        if actions.dim {
+            self.properties.send_brightness(config.dim_brightness())?;
         }
 
-        let (byte, bit) = match display_rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                let byte =
-                    &mut self.buffer[((y as usize) / 8 * display_width as usize) + (x as usize)];
-                let bit = 1 << (y % 8);
+        match config.screensaver() {
+            Screensaver::DisplayOff if actions.screensaver => self.properties.set_display_on(false),
+            Screensaver::Dots if actions.screensaver => self.flush(),
+            // Both axes are shifted in software, the frame has to be sent again
+            _ if actions.shift && shift != previous_shift => self.flush(),
+            _ => Ok(()),
+        }
+    }
 
-                (byte, bit)
-            }
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                let byte =
-                    &mut self.buffer[((x as usize) / 8 * display_width as usize) + (y as usize)];
-                let bit = 1 << (x % 8);
+    /// Leave the dimmed and screensaver states after the content changed
+    fn wake(&mut self, dimmed: bool, screensaver: bool) -> Result<(), DI::Error> {
+        if dimmed {
+            let brightness = self.properties.get_brightness();
+            self.properties.send_brightness(brightness)?;
+        }
+        if screensaver {
+            self.properties.set_display_on(true)?;
+        }
+        Ok(())
+    }
 
-                (byte, bit)
-            }
+    fn mark_changed(&mut self) {
+        if let Some(state) = self.burn_in.as_mut() {
+            state.mark_changed();
+        }
+    }
+
+    /// Turn a pixel on or off, combined with the current pixel using the
+    /// [raster operation](Self::set_raster_op). A non-zero `value` is treated as on, `0` as off.
+    /// If the X and Y coordinates are out of the bounds of the display, this method call is a
+    /// noop.
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: u8) {
+        let (column, row) = if self.column_major() { (y, x) } else { (x, y) };
+        let Some((idx, bit)) = self.raw_position(column, row) else {
+            return;
         };
 
-        if value == 0 {
-            *byte &= !bit;
-        } else {
-            *byte |= bit;
+        if self.write_bits(idx, bit, value != 0, self.raster_op) {
+            self.mark_changed();
         }
     }
 
+    /// Set how drawn pixels combine with the buffer contents. Applies to `set_pixel`, drawing and
+    /// fills, but not to `clear`. Defaults to [`RasterOp::Copy`].
+    pub fn set_raster_op(&mut self, raster_op: RasterOp) {
+        self.raster_op = raster_op;
+    }
+
+    /// Get the raster operation used for drawing
+    pub fn raster_op(&self) -> RasterOp {
+        self.raster_op
+    }
+
+    /// Apply `op` with an all on or all off source to the bits of `mask` in the buffer byte at
+    /// `idx`. Returns whether the byte changed.
+    fn write_bits(&mut self, idx: usize, mask: u8, value: bool, op: RasterOp) -> bool {
+        let byte = &mut self.buffer[idx];
+        let old = *byte;
+        *byte = op.apply(old, if value { 0xFF } else { 0x00 }, mask);
+        if *byte == old {
+            return false;
+        }
+
+        let (page, column) = if self.column_major() {
+            (idx % self.page_count(), idx / self.page_count())
+        } else {
+            (idx / self.page_len(), idx % self.page_len())
+        };
+        self.dirty.mark(page, column);
+        true
+    }
+
     /// Display is set up in column mode, i.e. a byte walks down a column of 8 pixels from
     /// column 0 on the left, to column _n_ on the right
     pub fn init(&mut self) -> Result<(), DI::Error> {
-        self.properties.init_column_mode()
+        self.properties.init_column_mode()?;
+        self.sync_addressing_mode()
     }
 
-    /// Get display dimensions, taking into account the current rotation of the display
+    /// Get display dimensions, taking into account the current rotation and zoom of the display
     pub fn get_dimensions(&self) -> (u8, u8) {
         self.properties.get_dimensions()
     }
 
-    /// Set the display rotation
+    /// Get the configured display size
+    pub fn get_size(&self) -> DisplaySize {
+        self.properties.get_size()
+    }
+
+    /// Set the display rotation. `Rotate90` and `Rotate270` store the buffer column by column and
+    /// switch the display to vertical addressing, so frames are sent without transposing. The
+    /// buffer is not converted, clear or redraw it after changing between portrait and landscape,
+    /// or use [`set_rotation_preserving`](Self::set_rotation_preserving).
     pub fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DI::Error> {
-        self.properties.set_rotation(rot)
+        self.dirty = DirtyColumns::ALL;
+        self.properties.set_rotation(rot)?;
+        self.sync_addressing_mode()
+    }
+
+    /// Set the display rotation and move the buffer contents into the new orientation, so every
+    /// pixel keeps its X and Y coordinates. Content outside the new dimensions, e.g. the right
+    /// half of a landscape screen turned to portrait, is dropped. Call `flush` to show the result.
+    ///
+    /// The buffer and the dimensions reported by `get_dimensions` change together, even if
+    /// sending the rotation commands fails.
+    pub fn set_rotation_preserving(&mut self, rot: DisplayRotation) -> Result<(), DI::Error> {
+        let display_size = self.properties.get_size();
+        let (old_width, old_height) = self.get_dimensions();
+        let old_column_major = self.column_major();
+        let old = self.buffer;
+
+        let result = self.properties.set_rotation(rot);
+
+        // Column remapping only applies to data written afterwards, every page has to be sent
+        // again
+        self.dirty = DirtyColumns::ALL;
+
+        let (width, height) = self.get_dimensions();
+        let column_major = self.column_major();
+        if column_major != old_column_major {
+            self.buffer = [0; BUFFER_SIZE];
+            for y in 0..old_height.min(height) as u32 {
+                for x in 0..old_width.min(width) as u32 {
+                    let (column, row) = if old_column_major { (y, x) } else { (x, y) };
+                    let Some((idx, bit)) =
+                        layout_position(display_size, old_column_major, column, row)
+                    else {
+                        continue;
+                    };
+                    if old[idx] & bit != 0 {
+                        let (column, row) = if column_major { (y, x) } else { (x, y) };
+                        if let Some((idx, bit)) =
+                            layout_position(display_size, column_major, column, row)
+                        {
+                            self.buffer[idx] |= bit;
+                        }
+                    }
+                }
+            }
+            self.mark_changed();
+        }
+
+        result?;
+        self.sync_addressing_mode()
+    }
+
+    /// Mirror the display horizontally and/or vertically, in the coordinates of the current
+    /// rotation. The buffer is unchanged, the display hardware flips the image. Column remapping
+    /// only applies to data written afterwards, so all pages are sent again by the next flush.
+    pub fn set_mirror(&mut self, horizontal: bool, vertical: bool) -> Result<(), DI::Error> {
+        self.dirty = DirtyColumns::ALL;
+        self.properties.set_mirror(horizontal, vertical)
+    }
+
+    /// Get the horizontal and vertical mirroring of the display
+    pub fn get_mirror(&self) -> (bool, bool) {
+        self.properties.get_mirror()
+    }
+
+    /// Use vertical addressing for the column-major buffer of `Rotate90` and `Rotate270`, and
+    /// horizontal addressing otherwise. Page addressing is left alone.
+    fn sync_addressing_mode(&mut self) -> Result<(), DI::Error> {
+        let addr_mode = match self.properties.get_addressing_mode() {
+            AddrMode::Page => return Ok(()),
+            _ if self.column_major() => AddrMode::Vertical,
+            _ => AddrMode::Horizontal,
+        };
+
+        if self.properties.get_addressing_mode() != addr_mode {
+            self.properties.set_addressing_mode(addr_mode)?;
+        }
+        Ok(())
     }
 
     /// Set the display contrast
     pub fn set_contrast(&mut self, contrast: u8) -> Result<(), DI::Error> {
         self.properties.set_contrast(contrast)
     }
+
+    /// Set the display RAM row shown on the top row of the display (0-63). Changing the start
+    /// line scrolls the image vertically without sending any data; rows wrap around the 64 rows
+    /// of display RAM.
+    pub fn set_start_line(&mut self, line: u8) -> Result<(), DI::Error> {
+        self.properties.set_start_line(line)
+    }
+
+    /// Get the display RAM row shown on the top row of the display
+    pub fn get_start_line(&self) -> u8 {
+        self.properties.get_start_line()
+    }
+
+    /// Scroll the image vertically by `pixels` rows by moving the display start line. Positive
+    /// values move the content up. Call repeatedly with small steps for smooth scrolling.
+    pub fn scroll_vertical_smooth(&mut self, pixels: i8) -> Result<(), DI::Error> {
+        let line = (self.properties.get_start_line() as i16 + pixels as i16).rem_euclid(64);
+        self.properties.set_start_line(line as u8)
+    }
+
+    /// Send a single page (8 pixel rows of display RAM) of the buffer to the display. Visible
+    /// pages are sent like `flush_step` sends them, including the burn-in protection pixel shift.
+    /// Pages outside the visible area, e.g. the hidden half of a 128x32 panel, are never shifted
+    /// and are sent as is.
+    pub fn flush_page(&mut self, page: u8) -> Result<(), DI::Error> {
+        let display_size = self.properties.get_size();
+        let (display_width, _) = display_size.dimensions();
+        let column_offset = display_size.column_offset();
+        let width = display_width as usize;
+
+        if (page as usize) < self.page_count() {
+            let length = self.active_len();
+            let column_major = self.column_major();
+            Self::send_frame(
+                &mut self.properties,
+                self.burn_in.as_ref(),
+                &self.buffer[..length],
+                column_major,
+                page as usize,
+                page as usize + 1,
+            )?;
+            self.dirty.clear_page(page as usize);
+            return Ok(());
+        }
+
+        // Only the row-major buffer holds pages outside the visible area
+        let start = page as usize * width;
+        if self.column_major() || start + width > BUFFER_SIZE {
+            return Ok(());
+        }
+
+        self.properties.set_draw_area(
+            (column_offset, page * 8),
+            (display_width + column_offset, page * 8 + 8),
+        )?;
+        self.properties.draw(&self.buffer[start..start + width])?;
+        self.dirty.clear_page(page as usize);
+        Ok(())
+    }
+
+    /// Get one page (8 pixel rows) of display RAM in the buffer, including pages outside the
+    /// visible area. Returns `None` for the column-major buffer of `Rotate90` and `Rotate270`.
+    pub(crate) fn page_mut(&mut self, page: u8) -> Option<&mut [u8]> {
+        if self.column_major() {
+            return None;
+        }
+
+        let width = self.page_len();
+        let start = page as usize * width;
+        if start + width > BUFFER_SIZE {
+            return None;
+        }
+
+        self.dirty.mark_page(page as usize);
+        self.mark_changed();
+        Some(&mut self.buffer[start..start + width])
+    }
+
+    /// Turn a pixel on or off addressed by display RAM column and row, ignoring rotation. Rows
+    /// may lie outside the visible area, e.g. in the hidden half of a 128x32 panel.
+    pub(crate) fn set_raw_pixel(&mut self, column: u32, row: u32, value: bool) {
+        let Some((idx, bit)) = self.raw_position(column, row) else {
+            return;
+        };
+
+        if self.write_bits(idx, bit, value, RasterOp::Copy) {
+            self.mark_changed();
+        }
+    }
+
+    /// Set the memory addressing mode used to send the buffer to the display. The buffer layout
+    /// stays the same, `flush` converts it as needed. Changing the rotation picks horizontal or
+    /// vertical addressing again to match the buffer layout.
+    pub fn set_addressing_mode(&mut self, addr_mode: AddrMode) -> Result<(), DI::Error> {
+        self.properties.set_addressing_mode(addr_mode)
+    }
+
+    /// Invert the display colours
+    pub fn set_invert(&mut self, invert: bool) -> Result<(), DI::Error> {
+        self.properties.set_invert(invert)
+    }
+
+    /// Set the display brightness
+    pub fn set_brightness(&mut self, brightness: Brightness) -> Result<(), DI::Error> {
+        self.properties.set_brightness(brightness)
+    }
+
+    /// Enable or disable zoom-in (double height) mode. While zoomed, `get_dimensions` reports
+    /// half the panel height and only the upper half of the buffer is visible.
+    pub fn set_zoom(&mut self, zoom: bool) -> Result<(), DI::Error> {
+        self.properties.set_zoom(zoom)
+    }
+}
+
+/// Bit mask of the rows in `rows` that fall within `page`
+#[cfg(feature = "graphics")]
+fn page_mask(rows: &Range<u32>, page: u32) -> u8 {
+    let first = rows.start.max(page * 8) - page * 8;
+    let last = rows.end.min(page * 8 + 8) - page * 8;
+
+    (0xFFu16 << first) as u8 & (0xFFu16 >> (8 - last)) as u8
 }
 
+#[cfg(feature = "graphics")]
+use crate::{canvas::Canvas, viewport::Viewport};
+#[cfg(feature = "graphics")]
+use core::ops::Range;
 #[cfg(feature = "graphics")]
 use embedded_graphics_core::{
     draw_target::DrawTarget,
     geometry::Size,
-    geometry::{Dimensions, OriginDimensions},
+    geometry::{Dimensions, OriginDimensions, Point},
+    image::GetPixel,
     pixelcolor::BinaryColor,
+    primitives::Rectangle,
     Pixel,
 };
 
+#[cfg(feature = "graphics")]
+impl<DI> GraphicsMode<DI>
+where
+    DI: DisplayInterface,
+{
+    /// Set every pixel in the buffer to `color`. This method does not update the display itself,
+    /// call `flush` to send the buffer.
+    pub fn clear_with(&mut self, color: BinaryColor) {
+        let length = self.active_len();
+        let fill = if color.is_on() { 0xFF } else { 0x00 };
+
+        self.buffer[..length].fill(fill);
+        self.dirty = DirtyColumns::ALL;
+        self.mark_changed();
+    }
+
+    /// Get the color of the pixel at `x`, `y` in the buffer, taking into account the current
+    /// rotation of the display. Returns `None` if the coordinates are out of bounds.
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<BinaryColor> {
+        let (width, height) = self.get_dimensions();
+        if x >= width as u32 || y >= height as u32 {
+            return None;
+        }
+
+        self.pixel_location(x, y)
+            .map(|(idx, bit)| BinaryColor::from(self.buffer[idx] & bit != 0))
+    }
+
+    /// Invert all pixels in `area`, e.g. to highlight a menu entry. Inverting the same area again
+    /// restores the original content.
+    pub fn invert_region(&mut self, area: Rectangle) {
+        let area = area.intersection(&self.bounding_box());
+
+        self.fill_area(
+            area.top_left.x as u32,
+            area.top_left.y as u32,
+            area.size.width,
+            area.size.height,
+            true,
+            RasterOp::Xor,
+        );
+    }
+
+    /// Get a drawing target for `area` of the display with its own origin and clipping, e.g. for
+    /// a status bar. The area is clipped to the display.
+    pub fn viewport(&mut self, area: Rectangle) -> Viewport<'_, DI> {
+        Viewport::new(self, area)
+    }
+
+    /// Whether any byte of display RAM covering `area` changed since it was last sent to the
+    /// display
+    pub fn is_area_dirty(&self, area: Rectangle) -> bool {
+        let Some((columns, pages)) = self.ram_window(area) else {
+            return false;
+        };
+
+        pages
+            .into_iter()
+            .any(|page| self.dirty.intersects(page as usize, &columns))
+    }
+
+    /// Send only the pages and columns of display RAM covering `area` to the display and mark
+    /// them as sent. While burn-in protection is active the whole frame is sent instead, as the
+    /// pixel shift moves the whole image.
+    pub fn flush_viewport(&mut self, area: Rectangle) -> Result<(), DI::Error> {
+        if self.burn_in.is_some() {
+            return self.flush();
+        }
+
+        let Some((columns, pages)) = self.ram_window(area) else {
+            return Ok(());
+        };
+        let result = self.send_window(&columns, &pages);
+        if result.is_ok() {
+            for page in pages {
+                self.dirty.clear(page as usize, &columns);
+            }
+        }
+        self.check_watchdog(result)
+    }
+
+    /// Columns and pages of display RAM covering `area`, or `None` if it lies outside the display
+    fn ram_window(&self, area: Rectangle) -> Option<(Range<u32>, Range<u32>)> {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return None;
+        }
+
+        let (x, y) = (area.top_left.x as u32, area.top_left.y as u32);
+        let (columns, rows) = if self.column_major() {
+            (y..y + area.size.height, x..x + area.size.width)
+        } else {
+            (x..x + area.size.width, y..y + area.size.height)
+        };
+
+        Some((columns, rows.start / 8..(rows.end - 1) / 8 + 1))
+    }
+
+    /// Send a window of display RAM columns and pages from the buffer
+    fn send_window(&mut self, columns: &Range<u32>, pages: &Range<u32>) -> Result<(), DI::Error> {
+        let display_size = self.properties.get_size();
+        let (display_width, display_height) = display_size.dimensions();
+        let column_offset = display_size.column_offset() as u32;
+        self.properties.set_draw_area(
+            ((columns.start + column_offset) as u8, pages.start as u8 * 8),
+            ((columns.end + column_offset) as u8, pages.end as u8 * 8),
+        )?;
+
+        let column_major = self.column_major();
+        let panel_pages = display_height as usize / 8;
+        let byte_at = |buffer: &[u8], column: u32, page: u32| {
+            if column_major {
+                buffer[column as usize * panel_pages + page as usize]
+            } else {
+                buffer[page as usize * display_width as usize + column as usize]
+            }
+        };
+
+        if self.properties.get_addressing_mode() == AddrMode::Vertical {
+            // One column at a time
+            let mut chunk = [0u8; 8];
+            let page_count = pages.len();
+            for column in columns.clone() {
+                for (page, out) in pages.clone().zip(chunk.iter_mut()) {
+                    *out = byte_at(&self.buffer, column, page);
+                }
+                self.properties.draw(&chunk[..page_count])?;
+            }
+        } else {
+            // One page at a time
+            let mut row = [0u8; 132];
+            let width = columns.len();
+            for page in pages.clone() {
+                for (column, out) in columns.clone().zip(row.iter_mut()) {
+                    *out = byte_at(&self.buffer, column, page);
+                }
+                self.properties.draw(&row[..width])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy the `src` area of `canvas` into the buffer with its top left corner at `dest`,
+    /// combining pixels with `rop`. Parts outside the canvas or the display are skipped. Whole
+    /// bytes are copied when the source and destination rows are equally aligned to pages, e.g.
+    /// both at a multiple of 8, and the display is not rotated by 90 or 270 degrees.
+    pub fn blit<const W: usize, const PAGES: usize>(
+        &mut self,
+        canvas: &Canvas<W, PAGES>,
+        src: Rectangle,
+        dest: Point,
+        rop: RasterOp,
+    ) {
+        let clipped = src.intersection(&canvas.bounding_box());
+        let dest = dest + (clipped.top_left - src.top_left);
+        let area = Rectangle::new(dest, clipped.size).intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return;
+        }
+        // Add to a destination position to get the source position
+        let offset = clipped.top_left - dest;
+
+        let (x, y) = (area.top_left.x as u32, area.top_left.y as u32);
+        let (width, height) = (area.size.width, area.size.height);
+        let mut changed = false;
+
+        if !self.column_major() && offset.y.rem_euclid(8) == 0 {
+            let display_width = self.page_len();
+            let rows = y..y + height;
+            for page in rows.start / 8..=(rows.end - 1) / 8 {
+                let mask = page_mask(&rows, page);
+                let src_page = &canvas.pages()[(page as i32 + offset.y / 8) as usize];
+                for column in x..x + width {
+                    let idx = page as usize * display_width + column as usize;
+                    let value = src_page[(column as i32 + offset.x) as usize];
+
+                    let old = self.buffer[idx];
+                    self.buffer[idx] = rop.apply(old, value, mask);
+                    if self.buffer[idx] != old {
+                        self.dirty.mark(page as usize, column as usize);
+                        changed = true;
+                    }
+                }
+            }
+        } else {
+            for row in y..y + height {
+                for column in x..x + width {
+                    let value = canvas
+                        .get_pixel(
+                            (column as i32 + offset.x) as u32,
+                            (row as i32 + offset.y) as u32,
+                        )
+                        .is_some_and(|color| color.is_on());
+                    if let Some((idx, bit)) = self.pixel_location(column, row) {
+                        changed |= self.write_bits(idx, bit, value, rop);
+                    }
+                }
+            }
+        }
+
+        if changed {
+            self.mark_changed();
+        }
+    }
+
+    /// Draw the area of `width` x `height` pixels at `x`, `y` on or off with `op`, a whole byte at
+    /// a time. The area must lie within `get_dimensions`.
+    fn fill_area(&mut self, x: u32, y: u32, width: u32, height: u32, value: bool, op: RasterOp) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let column_major = self.column_major();
+        // The area in display RAM columns and rows
+        let (columns, rows) = if column_major {
+            (y..y + height, x..x + width)
+        } else {
+            (x..x + width, y..y + height)
+        };
+        let (display_width, display_height) = self.properties.get_size().dimensions();
+        let pages = display_height as usize / 8;
+
+        let mut changed = false;
+        for page in rows.start / 8..=(rows.end - 1) / 8 {
+            let mask = page_mask(&rows, page);
+            for column in columns.clone() {
+                let idx = if column_major {
+                    column as usize * pages + page as usize
+                } else {
+                    page as usize * display_width as usize + column as usize
+                };
+                changed |= self.write_bits(idx, mask, value, op);
+            }
+        }
+
+        if changed {
+            self.mark_changed();
+        }
+    }
+}
+
 #[cfg(feature = "graphics")]
 impl<DI> DrawTarget for GraphicsMode<DI>
 where
@@ -226,6 +1311,86 @@ where
 
         Ok(())
     }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let drawable = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = drawable.bottom_right() else {
+            return Ok(());
+        };
+
+        let column_major = self.column_major();
+        let (display_width, display_height) = self.properties.get_size().dimensions();
+        let pages = display_height as usize / 8;
+
+        let mut changed = false;
+        let mut colors = colors.into_iter();
+        for y in area.rows() {
+            for x in area.columns() {
+                let Some(color) = colors.next() else {
+                    break;
+                };
+                if y < drawable.top_left.y
+                    || y > bottom_right.y
+                    || x < drawable.top_left.x
+                    || x > bottom_right.x
+                {
+                    continue;
+                }
+
+                // Inside the drawable area, so the position is valid for the buffer layout
+                let (x, y) = (x as usize, y as usize);
+                let (idx, bit) = if column_major {
+                    (y * pages + x / 8, 1 << (x % 8))
+                } else {
+                    (y / 8 * display_width as usize + x, 1 << (y % 8))
+                };
+                changed |= self.write_bits(idx, bit, color.is_on(), self.raster_op);
+            }
+        }
+
+        if changed {
+            self.mark_changed();
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+
+        self.fill_area(
+            area.top_left.x as u32,
+            area.top_left.y as u32,
+            area.size.width,
+            area.size.height,
+            color.is_on(),
+            self.raster_op,
+        );
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.clear_with(color);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<DI> GetPixel for GraphicsMode<DI>
+where
+    DI: DisplayInterface,
+{
+    type Color = BinaryColor;
+
+    fn pixel(&self, p: Point) -> Option<Self::Color> {
+        if p.x < 0 || p.y < 0 {
+            return None;
+        }
+
+        self.get_pixel(p.x as u32, p.y as u32)
+    }
 }
 
 #[cfg(feature = "graphics")]
@@ -239,3 +1404,426 @@ where
         Size::new(w.into(), h.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::mock::{MockHandle, MockInterface};
+
+    fn display(addr_mode: AddrMode) -> (GraphicsMode<MockInterface>, MockHandle) {
+        let (iface, state) = MockInterface::new();
+        let mut display = GraphicsMode::new(DisplayProperties::new(
+            iface,
+            DisplaySize::Display128x64NoOffset,
+            DisplayRotation::Rotate0,
+            AddrMode::Horizontal,
+        ));
+        display.init().unwrap();
+        display.set_addressing_mode(addr_mode).unwrap();
+        for (i, byte) in display.buffer_mut().iter_mut().enumerate() {
+            *byte = (i * 7 + i / 128) as u8;
+        }
+
+        (display, state)
+    }
+
+    fn assert_ram(display: &GraphicsMode<MockInterface>, state: &MockHandle) {
+        let state = state.borrow();
+        for page in 0..8 {
+            for column in 0..128 {
+                let idx = if display.column_major() {
+                    column * 8 + page
+                } else {
+                    page * 128 + column
+                };
+                assert_eq!(
+                    state.ram[page][column],
+                    display.buffer()[idx],
+                    "page {page} column {column}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn resume_flush_starts_at_failed_page() {
+        for addr_mode in [AddrMode::Horizontal, AddrMode::Vertical, AddrMode::Page] {
+            for fail_after in [0, 31, 100, 128, 300, 1023] {
+                let (mut display, state) = display(addr_mode);
+                state.borrow_mut().fail_data_after = Some(fail_after);
+
+                assert!(display.flush().is_err());
+                assert!(display.flush_pending());
+                // Only complete chunks count as sent, vertical mode starts over
+                let page = match addr_mode {
+                    AddrMode::Vertical => 0,
+                    _ => fail_after / 32 * 32 / 128,
+                };
+                assert_eq!(
+                    display.pending_page,
+                    Some(page),
+                    "{addr_mode:?} {fail_after}"
+                );
+
+                let before = state.borrow().data_bytes;
+                display.resume_flush().unwrap();
+                assert!(!display.flush_pending());
+                assert_eq!(state.borrow().data_bytes - before, (8 - page) * 128);
+                assert_ram(&display, &state);
+            }
+        }
+    }
+
+    #[test]
+    fn retried_flush_completes_frame() {
+        for addr_mode in [AddrMode::Horizontal, AddrMode::Vertical, AddrMode::Page] {
+            for fail_after in [0, 31, 100, 128, 300, 1023] {
+                let (mut display, state) = display(addr_mode);
+                state.borrow_mut().max_attempts = 2;
+                state.borrow_mut().fail_data_after = Some(fail_after);
+
+                display.flush().unwrap();
+                assert!(!display.flush_pending());
+                assert_ram(&display, &state);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn set_rotation_preserving_keeps_coordinates() {
+        let pattern = |x: u32, y: u32| (x * 3 + y * 5) % 7 < 3;
+
+        for (case, size) in [
+            DisplaySize::Display128x64NoOffset,
+            DisplaySize::Display128x32,
+            DisplaySize::Display132x64,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let (iface, _state) = MockInterface::new();
+            let mut display = GraphicsMode::new(DisplayProperties::new(
+                iface,
+                size,
+                DisplayRotation::Rotate0,
+                AddrMode::Horizontal,
+            ));
+            let (width, height) = display.get_dimensions();
+            for y in 0..height as u32 {
+                for x in 0..width as u32 {
+                    display.set_pixel(x, y, pattern(x, y).into());
+                }
+            }
+
+            // Pixels survive in the area common to all orientations so far
+            let (mut kept_width, mut kept_height) = (width as u32, height as u32);
+            for (step, rotation) in [
+                DisplayRotation::Rotate90,
+                DisplayRotation::Rotate270,
+                DisplayRotation::Rotate0,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                display.set_rotation_preserving(rotation).unwrap();
+
+                let (width, height) = display.get_dimensions();
+                kept_width = kept_width.min(width as u32);
+                kept_height = kept_height.min(height as u32);
+                for y in 0..height as u32 {
+                    for x in 0..width as u32 {
+                        let expected = x < kept_width && y < kept_height && pattern(x, y);
+                        assert_eq!(
+                            display.get_pixel(x, y),
+                            Some(BinaryColor::from(expected)),
+                            "size {case} step {step} ({x}, {y})"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn blit_matches_per_pixel_drawing() {
+        use crate::canvas::Canvas;
+        use embedded_graphics_core::geometry::Size;
+
+        let mut canvas = Canvas::<24, 3>::new();
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                canvas.set_pixel(x, y, (x * 5 + y * 3) % 4 == 0);
+            }
+        }
+
+        let cases = [
+            // Page aligned, whole bytes are copied
+            (
+                Rectangle::new(Point::zero(), Size::new(24, 24)),
+                Point::new(16, 8),
+            ),
+            (
+                Rectangle::new(Point::new(3, 8), Size::new(17, 13)),
+                Point::new(40, 16),
+            ),
+            // Unaligned rows
+            (
+                Rectangle::new(Point::zero(), Size::new(24, 24)),
+                Point::new(13, 5),
+            ),
+            (
+                Rectangle::new(Point::new(5, 3), Size::new(14, 17)),
+                Point::new(70, 8),
+            ),
+            // Clipped by the canvas and the display
+            (
+                Rectangle::new(Point::new(-4, -8), Size::new(40, 40)),
+                Point::new(-3, -6),
+            ),
+            (
+                Rectangle::new(Point::zero(), Size::new(24, 24)),
+                Point::new(116, 48),
+            ),
+        ];
+
+        for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90] {
+            for rop in [
+                RasterOp::Copy,
+                RasterOp::Or,
+                RasterOp::And,
+                RasterOp::Xor,
+                RasterOp::AndNot,
+            ] {
+                for (case, (src, dest)) in cases.into_iter().enumerate() {
+                    let (mut blitted, _) = display(AddrMode::Horizontal);
+                    let (mut drawn, _) = display(AddrMode::Horizontal);
+                    blitted.set_rotation(rotation).unwrap();
+                    drawn.set_rotation(rotation).unwrap();
+
+                    blitted.blit(&canvas, src, dest, rop);
+
+                    drawn.set_raster_op(rop);
+                    for y in src.rows() {
+                        for x in src.columns() {
+                            let (Ok(dx), Ok(dy)) = (
+                                u32::try_from(x - src.top_left.x + dest.x),
+                                u32::try_from(y - src.top_left.y + dest.y),
+                            ) else {
+                                continue;
+                            };
+                            if let Some(color) = canvas.get_pixel(x as u32, y as u32) {
+                                drawn.set_pixel(dx, dy, color.is_on().into());
+                            }
+                        }
+                    }
+
+                    assert!(blitted.buffer() == drawn.buffer(), "{rop:?} case {case}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn orientation_changes_mark_all_pages_dirty() {
+        let changes: [fn(&mut GraphicsMode<MockInterface>); 3] = [
+            |display| display.set_mirror(true, false).unwrap(),
+            |display| {
+                display
+                    .set_rotation_preserving(DisplayRotation::Rotate180)
+                    .unwrap()
+            },
+            |display| display.flush_from(&[0xFF; 1024]).unwrap(),
+        ];
+
+        for (case, change) in changes.into_iter().enumerate() {
+            let (mut display, state) = display(AddrMode::Horizontal);
+            display.flush().unwrap();
+            change(&mut display);
+
+            let before = state.borrow().data_bytes;
+            display.flush_begin().unwrap();
+            while display.flush_step().is_pending() {}
+            assert_eq!(state.borrow().data_bytes - before, 1024, "case {case}");
+            assert_ram(&display, &state);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn viewport_flush_sends_and_clears_its_columns() {
+        use embedded_graphics_core::geometry::Size;
+
+        let left = Rectangle::new(Point::new(8, 8), Size::new(32, 8));
+        let dot = Rectangle::new(Point::zero(), Size::new(4, 4));
+
+        // Each pair is side by side on the same pages of display RAM
+        for (rotation, right) in [
+            (DisplayRotation::Rotate0, Point::new(64, 8)),
+            (DisplayRotation::Rotate90, Point::new(8, 64)),
+        ] {
+            let right = Rectangle::new(right, left.size);
+            let (mut display, state) = display(AddrMode::Horizontal);
+            display.set_rotation(rotation).unwrap();
+            display.flush().unwrap();
+
+            display
+                .viewport(left)
+                .fill_solid(&dot, BinaryColor::On)
+                .unwrap();
+            // The dirty state is kept by the display
+            assert!(display.viewport(left).is_dirty());
+            assert!(!display.viewport(right).is_dirty());
+
+            display
+                .viewport(right)
+                .fill_solid(&dot, BinaryColor::On)
+                .unwrap();
+            let sent = state.borrow().data_bytes;
+            display.viewport(left).flush().unwrap();
+            // Only the columns of the viewport are sent, 32 columns of one page or 8 columns of
+            // four pages
+            assert_eq!(state.borrow().data_bytes - sent, 32);
+            assert!(!display.viewport(left).is_dirty());
+            assert!(display.viewport(right).is_dirty());
+
+            display.viewport(right).flush().unwrap();
+            assert!(!display.viewport(right).is_dirty());
+            assert_ram(&display, &state);
+
+            // Nothing left for an incremental flush
+            let sent = state.borrow().data_bytes;
+            display.flush_begin().unwrap();
+            while display.flush_step().is_pending() {}
+            assert_eq!(state.borrow().data_bytes, sent);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn viewport_flush_sends_whole_frame_with_burn_in_protection() {
+        use embedded_graphics_core::geometry::Size;
+
+        let status = Rectangle::new(Point::zero(), Size::new(128, 8));
+
+        let (mut display, state) = display(AddrMode::Horizontal);
+        display.enable_burn_in_protection(BurnInConfig::default());
+        display.flush().unwrap();
+        display.viewport(status).clear_with(BinaryColor::On);
+
+        let before = state.borrow().data_bytes;
+        display.viewport(status).flush().unwrap();
+        assert_eq!(state.borrow().data_bytes - before, 1024);
+        assert!(!display.viewport(status).is_dirty());
+    }
+
+    #[test]
+    fn start_line_scrolls_and_wraps() {
+        let (mut display, state) = display(AddrMode::Horizontal);
+
+        display.set_start_line(10).unwrap();
+        assert_eq!(state.borrow().start_line, 10);
+
+        for (pixels, line) in [(-20, 54), (15, 5), (63, 4), (-4, 0)] {
+            display.scroll_vertical_smooth(pixels).unwrap();
+            assert_eq!(display.get_start_line(), line, "{pixels}");
+            assert_eq!(state.borrow().start_line, line, "{pixels}");
+        }
+    }
+
+    #[test]
+    fn flush_page_applies_burn_in_shift() {
+        let (mut display, state) = display(AddrMode::Horizontal);
+        display.enable_burn_in_protection(BurnInConfig::new().with_pixel_shift(1, 1));
+        // Two orbit steps reach a diagonal offset, shifting rows across page boundaries
+        display.burn_in_tick(1).unwrap();
+        display.burn_in_tick(1).unwrap();
+
+        display.set_pixel(20, 23, 0);
+        display.set_pixel(21, 16, 1);
+        display.flush_page(2).unwrap();
+        let page = state.borrow().ram[2];
+
+        // A full flush sends the same shifted page
+        display.flush().unwrap();
+        assert_eq!(state.borrow().ram[2], page);
+    }
+
+    #[test]
+    fn burn_in_shift_moves_the_frame_and_blanks_the_edges() {
+        for addr_mode in [AddrMode::Horizontal, AddrMode::Vertical] {
+            let (mut display, state) = display(addr_mode);
+            display.enable_burn_in_protection(BurnInConfig::new().with_pixel_shift(1, 2));
+
+            // Every position of both orbit rings, including shifts up and down across pages
+            for _ in 0..17 {
+                display.burn_in_tick(1).unwrap();
+                let (dx, dy) = display.burn_in.unwrap().offset();
+                let state = state.borrow();
+                for y in 0..64 {
+                    for x in 0..128 {
+                        let (src_x, src_y) = (x - dx as i32, y - dy as i32);
+                        let expected = (0..128).contains(&src_x)
+                            && (0..64).contains(&src_y)
+                            && display.buffer()[(src_y / 8 * 128 + src_x) as usize]
+                                & 1 << (src_y % 8)
+                                != 0;
+                        let on = state.ram[y as usize / 8][x as usize] & 1 << (y % 8) != 0;
+                        assert_eq!(on, expected, "offset ({dx}, {dy}) pixel ({x}, {y})");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn flush_mirrors_converts_each_geometry() {
+        let (display, _state) = display(AddrMode::Horizontal);
+
+        let geometries = [
+            (DisplaySize::Display128x64NoOffset, DisplayRotation::Rotate0),
+            (DisplaySize::Display128x32, DisplayRotation::Rotate0),
+            // Shares the frame converted for the previous mirror
+            (DisplaySize::Display128x32, DisplayRotation::Rotate0),
+            (
+                DisplaySize::Display128x64NoOffset,
+                DisplayRotation::Rotate90,
+            ),
+            // Converted again after a different geometry
+            (DisplaySize::Display128x32, DisplayRotation::Rotate0),
+        ];
+        let mirrors = geometries.map(|(size, rotation)| {
+            let (iface, state) = MockInterface::new();
+            let mut mirror = DisplayProperties::new(iface, size, rotation, AddrMode::Horizontal);
+            mirror.init_column_mode().unwrap();
+            (mirror, state)
+        });
+        let states = mirrors.each_ref().map(|(_, state)| state.clone());
+        let mut mirrors = mirrors.map(|(mirror, _)| mirror);
+        display.flush_mirrors(&mut mirrors).unwrap();
+
+        for (i, ((size, rotation), state)) in geometries.into_iter().zip(states).enumerate() {
+            // The same pixels drawn on a display of the mirror's geometry
+            let (iface, expected) = MockInterface::new();
+            let mut reference = GraphicsMode::new(DisplayProperties::new(
+                iface,
+                size,
+                rotation,
+                AddrMode::Horizontal,
+            ));
+            reference.init().unwrap();
+            let (width, height) = reference.get_dimensions();
+            for y in 0..height as u32 {
+                for x in 0..width as u32 {
+                    let on = display.get_pixel(x, y) == Some(BinaryColor::On);
+                    reference.set_pixel(x, y, on as u8);
+                }
+            }
+            reference.flush().unwrap();
+
+            assert_eq!(state.borrow().ram, expected.borrow().ram, "mirror {i}");
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Temporal dithering grayscale mode
+//!
+//! The SSD1306 can only turn pixels on or off, but by showing a sequence of bit planes for
+//! different amounts of time the eye averages them into 4 gray levels. Plane 0 holds the low bit
+//! of each pixel's level and is shown for one time slot, plane 1 holds the high bit and is shown
+//! for two, giving 0/3, 1/3, 2/3 and 3/3 on-time.
+//!
+//! Call [`refresh_step`](GrayscaleMode::refresh_step) at a fixed rate, as fast as the bus allows
+//! (e.g. from a timer or the main loop). Each call is one time slot. Flicker is noticeable below
+//! roughly 60 slots per second.
+
+use crate::{
+    command::AddrMode,
+    displayrotation::DisplayRotation,
+    interface::DisplayInterface,
+    mode::{
+        displaymode::DisplayModeTrait,
+        graphics::{buffer_position, BUFFER_SIZE},
+    },
+    properties::DisplayProperties,
+};
+
+/// Number of bit planes
+const PLANES: usize = 2;
+
+/// Time slots each plane is shown for, indexed by plane
+const PLANE_WEIGHTS: [u8; PLANES] = [1, 2];
+
+/// Grayscale mode handler
+pub struct GrayscaleMode<DI>
+where
+    DI: DisplayInterface,
+{
+    properties: DisplayProperties<DI>,
+    planes: [[u8; BUFFER_SIZE]; PLANES],
+    plane: usize,
+    slot: u8,
+}
+
+impl<DI> DisplayModeTrait<DI> for GrayscaleMode<DI>
+where
+    DI: DisplayInterface,
+{
+    /// Create new GrayscaleMode instance
+    fn new(properties: DisplayProperties<DI>) -> Self {
+        GrayscaleMode {
+            properties,
+            planes: [[0; BUFFER_SIZE]; PLANES],
+            plane: 0,
+            slot: 0,
+        }
+    }
+
+    /// Release all resources used by GrayscaleMode
+    fn release(self) -> DisplayProperties<DI> {
+        self.properties
+    }
+}
+
+impl<DI> GrayscaleMode<DI>
+where
+    DI: DisplayInterface,
+{
+    /// Initialise the display like [`GraphicsMode::init`](super::GraphicsMode::init), then raise
+    /// the oscillator frequency to its maximum to reduce flicker
+    pub fn init(&mut self) -> Result<(), DI::Error> {
+        self.properties.init_column_mode()?;
+        // Frames are sent in page order
+        self.properties.set_addressing_mode(AddrMode::Horizontal)?;
+        self.properties.set_clock_div(0xF, 0x0)
+    }
+
+    /// Clear all planes. The display is updated by the following `refresh_step` calls.
+    pub fn clear(&mut self) {
+        self.planes = [[0; BUFFER_SIZE]; PLANES];
+    }
+
+    /// Set a pixel to a gray level from 0 (off) to 3 (fully on). Levels above 3 are clamped. If
+    /// the X and Y coordinates are out of the bounds of the display, this method call is a noop.
+    pub fn set_pixel(&mut self, x: u32, y: u32, level: u8) {
+        let Some((idx, bit)) = buffer_position(
+            self.properties.get_size(),
+            self.properties.get_rotation(),
+            x,
+            y,
+        ) else {
+            return;
+        };
+        let level = level.min(3);
+
+        for (plane_idx, plane) in self.planes.iter_mut().enumerate() {
+            if level & (1 << plane_idx) == 0 {
+                plane[idx] &= !bit;
+            } else {
+                plane[idx] |= bit;
+            }
+        }
+    }
+
+    /// Advance by one time slot. The next bit plane is sent to the display when the current one
+    /// has been shown for its full weight, otherwise this is a noop. Returns `true` when a plane
+    /// was sent.
+    pub fn refresh_step(&mut self) -> Result<bool, DI::Error> {
+        if self.slot > 0 {
+            self.slot -= 1;
+            return Ok(false);
+        }
+
+        self.plane = (self.plane + 1) % PLANES;
+        self.slot = PLANE_WEIGHTS[self.plane] - 1;
+        self.flush_plane(self.plane)?;
+
+        Ok(true)
+    }
+
+    fn flush_plane(&mut self, plane: usize) -> Result<(), DI::Error> {
+        let display_size = self.properties.get_size();
+
+        let (display_width, display_height) = display_size.dimensions();
+        let column_offset = display_size.column_offset();
+        self.properties.set_draw_area(
+            (column_offset, 0),
+            (display_width + column_offset, display_height),
+        )?;
+
+        let length = (display_width as usize) * (display_height as usize) / 8;
+
+        self.properties.draw(&self.planes[plane][..length])
+    }
+
+    /// Get display dimensions, taking into account the current rotation and zoom of the display
+    pub fn get_dimensions(&self) -> (u8, u8) {
+        self.properties.get_dimensions()
+    }
+
+    /// Set the display rotation
+    pub fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DI::Error> {
+        self.properties.set_rotation(rot)
+    }
+}
+
+#[cfg(feature = "graphics")]
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::Size,
+    geometry::{Dimensions, OriginDimensions},
+    pixelcolor::{Gray2, GrayColor},
+    Pixel,
+};
+
+#[cfg(feature = "graphics")]
+impl<DI> DrawTarget for GrayscaleMode<DI>
+where
+    DI: DisplayInterface,
+{
+    type Color = Gray2;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bb = self.bounding_box();
+
+        pixels
+            .into_iter()
+            .filter(|Pixel(pos, _color)| bb.contains(*pos))
+            .for_each(|Pixel(pos, color)| self.set_pixel(pos.x as u32, pos.y as u32, color.luma()));
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<DI> OriginDimensions for GrayscaleMode<DI>
+where
+    DI: DisplayInterface,
+{
+    fn size(&self) -> Size {
+        let (w, h) = self.get_dimensions();
+
+        Size::new(w.into(), h.into())
+    }
+}
@@ -6,6 +6,16 @@
 
 pub mod displaymode;
 pub mod graphics;
+pub mod grayscale;
+pub mod paged;
+pub mod pageflip;
 pub mod raw;
+#[cfg(feature = "graphics")]
+pub mod virtualmode;
 
-pub use self::{graphics::GraphicsMode, raw::RawMode};
+pub use self::{
+    graphics::GraphicsMode, grayscale::GrayscaleMode, paged::PagedMode, pageflip::PageFlipMode,
+    raw::RawMode,
+};
+#[cfg(feature = "graphics")]
+pub use self::virtualmode::VirtualMode;
@@ -0,0 +1,379 @@
+//! Page buffer display mode for devices with little RAM
+//!
+//! `PagedMode` only keeps a single page (8 pixel rows) of the frame in memory instead of the
+//! whole frame like [`GraphicsMode`](super::GraphicsMode). A frame is rendered by calling the
+//! draw closure once for every page. Each call draws the whole scene into a [`PageTarget`] that
+//! keeps only the pixels of the current page, which is then sent to the display before the next
+//! page is rendered. The bounding box of the target is the current page, so shapes outside it are
+//! skipped before any pixel is drawn.
+//!
+//! ```rust,ignore
+//! let mut display: PagedMode<_> = Builder::new().connect_i2c(i2c).into();
+//!
+//! display.init().unwrap();
+//! display
+//!     .draw(|page| {
+//!         Circle::new(Point::new(40, 8), 48)
+//!             .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+//!             .draw(page)
+//!             .unwrap();
+//!     })
+//!     .unwrap();
+//! ```
+
+use crate::{
+    command::AddrMode,
+    displayrotation::DisplayRotation,
+    displaysize::DisplaySize,
+    interface::DisplayInterface,
+    mode::displaymode::DisplayModeTrait,
+    properties::DisplayProperties,
+};
+
+/// Length of the page buffer, the width of the widest supported display
+const PAGE_LEN: usize = 132;
+
+/// Page buffer mode handler
+pub struct PagedMode<DI>
+where
+    DI: DisplayInterface,
+{
+    properties: DisplayProperties<DI>,
+    buffer: [u8; PAGE_LEN],
+}
+
+impl<DI> DisplayModeTrait<DI> for PagedMode<DI>
+where
+    DI: DisplayInterface,
+{
+    /// Create new PagedMode instance
+    fn new(properties: DisplayProperties<DI>) -> Self {
+        PagedMode {
+            properties,
+            buffer: [0; PAGE_LEN],
+        }
+    }
+
+    /// Release all resources used by PagedMode
+    fn release(self) -> DisplayProperties<DI> {
+        self.properties
+    }
+}
+
+impl<DI> PagedMode<DI>
+where
+    DI: DisplayInterface,
+{
+    /// Initialise the display. Pages are sent one after the other.
+    pub fn init(&mut self) -> Result<(), DI::Error> {
+        self.properties.init_column_mode()?;
+        self.properties.set_addressing_mode(AddrMode::Horizontal)
+    }
+
+    /// Render and send a frame. `draw` is called once per page with a target that is as large as
+    /// the display but only keeps the pixels of that page, so it has to draw the same scene every
+    /// time. Each page is sent before the next one is rendered.
+    pub fn draw<F>(&mut self, mut draw: F) -> Result<(), DI::Error>
+    where
+        F: FnMut(&mut PageTarget<'_>),
+    {
+        let display_size = self.properties.get_size();
+        let (display_width, display_height) = display_size.dimensions();
+        let column_offset = display_size.column_offset();
+        self.properties.set_draw_area(
+            (column_offset, 0),
+            (display_width + column_offset, display_height),
+        )?;
+
+        let width = display_width as usize;
+        let (logical_width, logical_height) = self.properties.get_dimensions();
+        for page in 0..display_height / 8 {
+            let buffer = &mut self.buffer[..width];
+            buffer.fill(0);
+
+            let mut target = PageTarget {
+                buffer,
+                page,
+                rotation: self.properties.get_rotation(),
+                dimensions: (logical_width, logical_height),
+            };
+            draw(&mut target);
+
+            self.properties.draw(&self.buffer[..width])?;
+        }
+        Ok(())
+    }
+
+    /// Get display dimensions, taking into account the current rotation of the display
+    pub fn get_dimensions(&self) -> (u8, u8) {
+        self.properties.get_dimensions()
+    }
+
+    /// Get the configured display size
+    pub fn get_size(&self) -> DisplaySize {
+        self.properties.get_size()
+    }
+
+    /// Set the display rotation
+    pub fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DI::Error> {
+        self.properties.set_rotation(rot)
+    }
+
+    /// Set the display contrast
+    pub fn set_contrast(&mut self, contrast: u8) -> Result<(), DI::Error> {
+        self.properties.set_contrast(contrast)
+    }
+}
+
+/// Drawing target for a single page of a [`PagedMode`] frame. It uses the coordinates of the whole
+/// display, but its bounding box only covers the rows of the current page, or the columns when
+/// rotated by 90 or 270 degrees. Pixels outside the page are ignored.
+pub struct PageTarget<'a> {
+    buffer: &'a mut [u8],
+    page: u8,
+    rotation: DisplayRotation,
+    dimensions: (u8, u8),
+}
+
+impl PageTarget<'_> {
+    /// Index of the page of display RAM being rendered
+    pub fn page(&self) -> u8 {
+        self.page
+    }
+
+    /// Turn a pixel on or off. A non-zero `value` is treated as on, `0` as off. Pixels outside
+    /// the display or the current page are ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: u8) {
+        let (width, height) = self.dimensions;
+        if x >= width as u32 || y >= height as u32 {
+            return;
+        }
+
+        let (column, row) = match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (x, y),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (y, x),
+        };
+        if row / 8 != self.page as u32 {
+            return;
+        }
+
+        let Some(byte) = self.buffer.get_mut(column as usize) else {
+            return;
+        };
+        if value == 0 {
+            *byte &= !(1 << (row % 8));
+        } else {
+            *byte |= 1 << (row % 8);
+        }
+    }
+}
+
+#[cfg(feature = "graphics")]
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::Size,
+    geometry::{Dimensions, OriginDimensions, Point},
+    pixelcolor::BinaryColor,
+    primitives::Rectangle,
+    Pixel,
+};
+
+#[cfg(feature = "graphics")]
+impl DrawTarget for PageTarget<'_> {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bb = self.bounding_box();
+
+        pixels
+            .into_iter()
+            .filter(|Pixel(pos, _color)| bb.contains(*pos))
+            .for_each(|Pixel(pos, color)| {
+                self.set_pixel(pos.x as u32, pos.y as u32, color.is_on().into())
+            });
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let drawable = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = drawable.bottom_right() else {
+            return Ok(());
+        };
+
+        // Colors after the last drawable row are not needed
+        let mut colors = colors.into_iter();
+        for y in area.rows().take_while(|&y| y <= bottom_right.y) {
+            for x in area.columns() {
+                let Some(color) = colors.next() else {
+                    return Ok(());
+                };
+                if drawable.contains(Point::new(x, y)) {
+                    self.set_pixel(x as u32, y as u32, color.is_on().into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let drawable = area.intersection(&self.bounding_box());
+        if drawable.is_zero_sized() {
+            return Ok(());
+        }
+
+        // The area in display RAM columns and rows, all rows lie within the page
+        let (x, y) = (drawable.top_left.x as u32, drawable.top_left.y as u32);
+        let (columns, rows) = match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                (x..x + drawable.size.width, y..y + drawable.size.height)
+            }
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                (y..y + drawable.size.height, x..x + drawable.size.width)
+            }
+        };
+        let first = rows.start % 8;
+        let mask = (0xFFu16 << first) as u8 & (0xFFu16 >> (8 - first - rows.len() as u32)) as u8;
+
+        for byte in &mut self.buffer[columns.start as usize..columns.end as usize] {
+            if color.is_on() {
+                *byte |= mask;
+            } else {
+                *byte &= !mask;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl Dimensions for PageTarget<'_> {
+    /// The rows of the display in the current page, or the columns when rotated by 90 or 270
+    /// degrees
+    fn bounding_box(&self) -> Rectangle {
+        let (width, height) = self.dimensions;
+        let start = self.page as i32 * 8;
+        let display = Rectangle::new(Point::zero(), Size::new(width.into(), height.into()));
+
+        let page = match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                Rectangle::new(Point::new(0, start), Size::new(width.into(), 8))
+            }
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                Rectangle::new(Point::new(start, 0), Size::new(8, height.into()))
+            }
+        };
+        page.intersection(&display)
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<DI> OriginDimensions for PagedMode<DI>
+where
+    DI: DisplayInterface,
+{
+    fn size(&self) -> Size {
+        let (w, h) = self.get_dimensions();
+
+        Size::new(w.into(), h.into())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "graphics")]
+mod tests {
+    use super::*;
+    use crate::{interface::mock::MockInterface, mode::GraphicsMode};
+    use core::convert::Infallible;
+
+    /// Shapes crossing page boundaries, drawn with all drawing methods
+    fn scene<D>(target: &mut D)
+    where
+        D: DrawTarget<Color = BinaryColor, Error = Infallible>,
+    {
+        let on = BinaryColor::On;
+        target
+            .fill_solid(&Rectangle::new(Point::new(3, 5), Size::new(40, 21)), on)
+            .unwrap();
+        target
+            .fill_solid(
+                &Rectangle::new(Point::new(10, 9), Size::new(7, 3)),
+                BinaryColor::Off,
+            )
+            .unwrap();
+        target
+            .fill_contiguous(
+                &Rectangle::new(Point::new(-4, 20), Size::new(50, 30)),
+                (0..50 * 30).map(|i| BinaryColor::from(i % 3 == 0)),
+            )
+            .unwrap();
+        target
+            .draw_iter((0..200).map(|i| Pixel(Point::new(i % 70, i / 3), on)))
+            .unwrap();
+    }
+
+    #[test]
+    fn paged_output_matches_graphics_mode() {
+        for rotation in [
+            DisplayRotation::Rotate0,
+            DisplayRotation::Rotate90,
+            DisplayRotation::Rotate180,
+            DisplayRotation::Rotate270,
+        ] {
+            let (iface, graphics_state) = MockInterface::new();
+            let mut graphics = GraphicsMode::new(DisplayProperties::new(
+                iface,
+                DisplaySize::Display128x64NoOffset,
+                rotation,
+                AddrMode::Horizontal,
+            ));
+            graphics.init().unwrap();
+            scene(&mut graphics);
+            graphics.flush().unwrap();
+
+            let (iface, paged_state) = MockInterface::new();
+            let mut paged = PagedMode::new(DisplayProperties::new(
+                iface,
+                DisplaySize::Display128x64NoOffset,
+                rotation,
+                AddrMode::Horizontal,
+            ));
+            paged.init().unwrap();
+            paged.draw(|page| scene(page)).unwrap();
+
+            let ram = graphics_state.borrow().ram;
+            assert!(ram.iter().flatten().any(|&byte| byte != 0));
+            assert!(paged_state.borrow().ram == ram);
+        }
+    }
+
+    #[test]
+    fn page_target_is_clipped_to_its_page() {
+        let mut buffer = [0u8; 128];
+        for (rotation, dimensions) in [
+            (DisplayRotation::Rotate0, (128, 64)),
+            (DisplayRotation::Rotate90, (64, 128)),
+        ] {
+            let target = PageTarget {
+                buffer: &mut buffer,
+                page: 2,
+                rotation,
+                dimensions,
+            };
+            let expected = match rotation {
+                DisplayRotation::Rotate0 => Rectangle::new(Point::new(0, 16), Size::new(128, 8)),
+                _ => Rectangle::new(Point::new(16, 0), Size::new(8, 128)),
+            };
+            assert_eq!(target.bounding_box(), expected);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Double buffered display mode for 128x32 panels
+//!
+//! A 128x32 panel only shows 32 of the 64 rows of display RAM. `PageFlipMode` sends each new frame
+//! to the hidden half of the display RAM and then switches to it by changing the display start
+//! line, so the whole frame appears at once instead of visibly wiping down the screen.
+//!
+//! On panels that use all 64 rows there is no hidden half, frames are sent to the visible rows
+//! like [`GraphicsMode`](super::GraphicsMode) does.
+//!
+//! ```rust,ignore
+//! let mut display: PageFlipMode<_> = Builder::new()
+//!     .with_size(DisplaySize::Display128x32)
+//!     .connect_i2c(i2c)
+//!     .into();
+//!
+//! display.init().unwrap();
+//! loop {
+//!     display.clear();
+//!     // draw the next frame ...
+//!     display.flip().unwrap();
+//! }
+//! ```
+
+use crate::{
+    command::AddrMode,
+    displayrotation::DisplayRotation,
+    displaysize::DisplaySize,
+    interface::DisplayInterface,
+    mode::{
+        displaymode::DisplayModeTrait,
+        graphics::{buffer_position, BUFFER_SIZE},
+    },
+    properties::DisplayProperties,
+};
+
+/// Number of rows of display RAM
+const RAM_ROWS: u8 = 64;
+
+/// Page flipping mode handler
+pub struct PageFlipMode<DI>
+where
+    DI: DisplayInterface,
+{
+    properties: DisplayProperties<DI>,
+    buffer: [u8; BUFFER_SIZE],
+    front_row: u8,
+}
+
+impl<DI> DisplayModeTrait<DI> for PageFlipMode<DI>
+where
+    DI: DisplayInterface,
+{
+    /// Create new PageFlipMode instance
+    fn new(properties: DisplayProperties<DI>) -> Self {
+        PageFlipMode {
+            properties,
+            buffer: [0; BUFFER_SIZE],
+            front_row: 0,
+        }
+    }
+
+    /// Release all resources used by PageFlipMode
+    fn release(self) -> DisplayProperties<DI> {
+        self.properties
+    }
+}
+
+impl<DI> PageFlipMode<DI>
+where
+    DI: DisplayInterface,
+{
+    /// Initialise the display and show the lower half of display RAM first
+    pub fn init(&mut self) -> Result<(), DI::Error> {
+        self.properties.init_column_mode()?;
+        // Frames are sent in page order
+        self.properties.set_addressing_mode(AddrMode::Horizontal)?;
+        self.front_row = 0;
+        self.properties.set_start_line(0)
+    }
+
+    /// Clear the buffer of the next frame
+    pub fn clear(&mut self) {
+        self.buffer = [0; BUFFER_SIZE];
+    }
+
+    /// Whether the panel leaves a hidden half of display RAM to draw into
+    pub fn is_double_buffered(&self) -> bool {
+        let (_, display_height) = self.properties.get_size().dimensions();
+
+        display_height * 2 <= RAM_ROWS
+    }
+
+    /// Send the next frame to the hidden half of display RAM and show it. Without a hidden half
+    /// the frame is sent to the visible rows instead.
+    pub fn flip(&mut self) -> Result<(), DI::Error> {
+        let display_size = self.properties.get_size();
+        let (display_width, display_height) = display_size.dimensions();
+        let column_offset = display_size.column_offset();
+
+        let back_row = if self.is_double_buffered() {
+            (self.front_row + display_height) % RAM_ROWS
+        } else {
+            self.front_row
+        };
+
+        self.properties.set_draw_area(
+            (column_offset, back_row),
+            (display_width + column_offset, back_row + display_height),
+        )?;
+
+        let length = (display_width as usize) * (display_height as usize) / 8;
+        self.properties.draw(&self.buffer[..length])?;
+
+        self.properties.set_start_line(back_row)?;
+        self.front_row = back_row;
+
+        Ok(())
+    }
+
+    /// Turn a pixel of the next frame on or off. A non-zero `value` is treated as on, `0` as off.
+    /// If the X and Y coordinates are out of the bounds of the display, this method call is a
+    /// noop.
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: u8) {
+        let Some((idx, bit)) = buffer_position(
+            self.properties.get_size(),
+            self.properties.get_rotation(),
+            x,
+            y,
+        ) else {
+            return;
+        };
+
+        if value == 0 {
+            self.buffer[idx] &= !bit;
+        } else {
+            self.buffer[idx] |= bit;
+        }
+    }
+
+    /// Get display dimensions, taking into account the current rotation of the display
+    pub fn get_dimensions(&self) -> (u8, u8) {
+        self.properties.get_dimensions()
+    }
+
+    /// Get the configured display size
+    pub fn get_size(&self) -> DisplaySize {
+        self.properties.get_size()
+    }
+
+    /// Set the display rotation
+    pub fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DI::Error> {
+        self.properties.set_rotation(rot)
+    }
+
+    /// Set the display contrast
+    pub fn set_contrast(&mut self, contrast: u8) -> Result<(), DI::Error> {
+        self.properties.set_contrast(contrast)
+    }
+}
+
+#[cfg(feature = "graphics")]
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::Size,
+    geometry::{Dimensions, OriginDimensions},
+    pixelcolor::BinaryColor,
+    Pixel,
+};
+
+#[cfg(feature = "graphics")]
+impl<DI> DrawTarget for PageFlipMode<DI>
+where
+    DI: DisplayInterface,
+{
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bb = self.bounding_box();
+
+        pixels
+            .into_iter()
+            .filter(|Pixel(pos, _color)| bb.contains(*pos))
+            .for_each(|Pixel(pos, color)| {
+                self.set_pixel(pos.x as u32, pos.y as u32, color.is_on().into())
+            });
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<DI> OriginDimensions for PageFlipMode<DI>
+where
+    DI: DisplayInterface,
+{
+    fn size(&self) -> Size {
+        let (w, h) = self.get_dimensions();
+
+        Size::new(w.into(), h.into())
+    }
+}
@@ -0,0 +1,190 @@
+//! Virtual frame buffer larger than the display
+//!
+//! `VirtualMode` draws into a [`Canvas`] that can be larger than the panel, e.g. 512x64 pixels
+//! for a long table. Only the window at the current viewport is sent by `flush`, so scrolling
+//! means moving the viewport and flushing again, without redrawing any content.
+//!
+//! The viewport works in display RAM coordinates and only supports `Rotate0` and `Rotate180`.
+//!
+//! ```rust,ignore
+//! // 512 pixels wide and 8 pages, 64 pixels, high
+//! let mut display: VirtualMode<_, 512, 8> = Builder::new().connect_i2c(i2c).into();
+//!
+//! display.init().unwrap();
+//! // draw the whole table once ...
+//! for x in 0..=(512 - 128) {
+//!     display.set_viewport(Point::new(x, 0));
+//!     display.flush().unwrap();
+//! }
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    pixelcolor::BinaryColor,
+    Pixel,
+};
+
+use crate::{
+    canvas::Canvas,
+    command::AddrMode,
+    displaysize::DisplaySize,
+    interface::DisplayInterface,
+    mode::displaymode::DisplayModeTrait,
+    properties::DisplayProperties,
+};
+
+/// Virtual frame buffer mode handler, `W` pixels wide and `PAGES` pages (8 pixel rows each) high
+pub struct VirtualMode<DI, const W: usize, const PAGES: usize>
+where
+    DI: DisplayInterface,
+{
+    properties: DisplayProperties<DI>,
+    canvas: Canvas<W, PAGES>,
+    viewport: Point,
+}
+
+impl<DI, const W: usize, const PAGES: usize> DisplayModeTrait<DI> for VirtualMode<DI, W, PAGES>
+where
+    DI: DisplayInterface,
+{
+    /// Create new VirtualMode instance
+    fn new(properties: DisplayProperties<DI>) -> Self {
+        VirtualMode {
+            properties,
+            canvas: Canvas::new(),
+            viewport: Point::zero(),
+        }
+    }
+
+    /// Release all resources used by VirtualMode
+    fn release(self) -> DisplayProperties<DI> {
+        self.properties
+    }
+}
+
+impl<DI, const W: usize, const PAGES: usize> VirtualMode<DI, W, PAGES>
+where
+    DI: DisplayInterface,
+{
+    /// Initialise the display. The window is sent in page order.
+    pub fn init(&mut self) -> Result<(), DI::Error> {
+        self.properties.init_column_mode()?;
+        self.properties.set_addressing_mode(AddrMode::Horizontal)
+    }
+
+    /// Clear the virtual frame buffer. This method does not update the display itself.
+    pub fn clear(&mut self) {
+        self.canvas.clear();
+    }
+
+    /// Move the top left corner of the visible window to `position` in the virtual frame buffer.
+    /// The window is kept within the frame buffer. Call `flush` to show it.
+    pub fn set_viewport(&mut self, position: Point) {
+        let (display_width, display_height) = self.properties.get_size().dimensions();
+        let max_x = (self.canvas.width() as i32 - display_width as i32).max(0);
+        let max_y = (self.canvas.height() as i32 - display_height as i32).max(0);
+
+        self.viewport = Point::new(position.x.clamp(0, max_x), position.y.clamp(0, max_y));
+    }
+
+    /// Get the top left corner of the visible window in the virtual frame buffer
+    pub fn viewport(&self) -> Point {
+        self.viewport
+    }
+
+    /// Send the visible window of the virtual frame buffer to the display
+    pub fn flush(&mut self) -> Result<(), DI::Error> {
+        let display_size = self.properties.get_size();
+        let (display_width, display_height) = display_size.dimensions();
+        let column_offset = display_size.column_offset();
+        self.properties.set_draw_area(
+            (column_offset, 0),
+            (display_width + column_offset, display_height),
+        )?;
+
+        let width = display_width as usize;
+        let x = self.viewport.x as usize;
+        let shift = self.viewport.y as usize % 8;
+        let pages = self.canvas.pages();
+
+        // Columns beyond a narrow frame buffer stay off
+        let mut row = [0u8; 132];
+        for page in 0..display_height as usize / 8 {
+            let first = self.viewport.y as usize / 8 + page;
+            for (column, out) in row[..width].iter_mut().enumerate() {
+                let byte = |page: usize| {
+                    pages
+                        .get(page)
+                        .and_then(|bytes| bytes.get(x + column))
+                        .copied()
+                        .unwrap_or(0)
+                };
+                // Rows that don't start on a page boundary are spread over two pages
+                *out = if shift == 0 {
+                    byte(first)
+                } else {
+                    byte(first) >> shift | byte(first + 1) << (8 - shift)
+                };
+            }
+            self.properties.draw(&row[..width])?;
+        }
+        Ok(())
+    }
+
+    /// Get the virtual frame buffer
+    pub fn canvas(&self) -> &Canvas<W, PAGES> {
+        &self.canvas
+    }
+
+    /// Get the virtual frame buffer for drawing
+    pub fn canvas_mut(&mut self) -> &mut Canvas<W, PAGES> {
+        &mut self.canvas
+    }
+
+    /// Turn a pixel of the virtual frame buffer on or off. A non-zero `value` is treated as on,
+    /// `0` as off. Pixels outside the frame buffer are ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: u8) {
+        self.canvas.set_pixel(x, y, value != 0);
+    }
+
+    /// Get the panel dimensions, i.e. the size of the visible window
+    pub fn get_dimensions(&self) -> (u8, u8) {
+        self.properties.get_size().dimensions()
+    }
+
+    /// Get the configured display size
+    pub fn get_size(&self) -> DisplaySize {
+        self.properties.get_size()
+    }
+
+    /// Set the display contrast
+    pub fn set_contrast(&mut self, contrast: u8) -> Result<(), DI::Error> {
+        self.properties.set_contrast(contrast)
+    }
+}
+
+impl<DI, const W: usize, const PAGES: usize> DrawTarget for VirtualMode<DI, W, PAGES>
+where
+    DI: DisplayInterface,
+{
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.canvas.draw_iter(pixels)
+    }
+}
+
+impl<DI, const W: usize, const PAGES: usize> OriginDimensions for VirtualMode<DI, W, PAGES>
+where
+    DI: DisplayInterface,
+{
+    /// The size of the whole virtual frame buffer
+    fn size(&self) -> Size {
+        self.canvas.size()
+    }
+}
@@ -2,8 +2,13 @@
 
 
 pub use super::{
+    brightness::Brightness,
     displayrotation::DisplayRotation,
     displaysize::DisplaySize,
     interface::I2cInterface,
-    mode::GraphicsMode,
+    mode::{GraphicsMode, GrayscaleMode, PageFlipMode, PagedMode},
+    rasterop::RasterOp,
 };
+
+#[cfg(feature = "graphics")]
+pub use super::mode::VirtualMode;
@@ -2,7 +2,7 @@
 
 
 use crate::{
-    command::{Command, VcomhLevel, AddrMode},
+    command::{AddrMode, Command, CommandBatch, VcomhLevel},
     displayrotation::DisplayRotation,
     displaysize::DisplaySize,
     interface::DisplayInterface,
@@ -10,16 +10,28 @@ use crate::{
 
 use crate::brightness::Brightness;
 
+/// Highest column address of the SSD1306
+const MAX_COLUMN: u8 = 127;
+
 /// Display properties struct
 pub struct DisplayProperties<DI> {
     iface: DI,
     addr_mode: AddrMode,
     display_size: DisplaySize,
     display_rotation: DisplayRotation,
+    mirror: (bool, bool),
     draw_area_start: (u8, u8),
     draw_area_end: (u8, u8),
     draw_column: u8,
     draw_row: u8,
+    zoom: bool,
+    brightness: Brightness,
+    invert: bool,
+    display_offset: u8,
+    display_on: bool,
+    clock_div: (u8, u8),
+    start_line: u8,
+    narrowed: bool,
 }
 
 impl<DI> DisplayProperties<DI>
@@ -38,15 +50,27 @@ where
             display_size,
             addr_mode,
             display_rotation,
+            mirror: (false, false),
             draw_area_start: (0, 0),
             draw_area_end: (0, 0),
             draw_column: 0,
             draw_row: 0,
+            zoom: false,
+            brightness: Brightness::default(),
+            invert: false,
+            display_offset: 0,
+            display_on: true,
+            clock_div: (0x8, 0x0),
+            start_line: 0,
+            narrowed: false,
         }
     }
 
     /// Initialise the display in column mode (i.e. a byte walks down a column of 8 pixels) with
     /// column 0 on the left and column _(display_width - 1)_ on the right.
+    ///
+    /// Runtime settings applied so far (addressing mode, rotation, mirror, brightness, invert,
+    /// zoom, offset, start line, clock) are replayed, so this can also be used to restore a display that lost power.
     pub fn init_column_mode(&mut self) -> Result<(), DI::Error> {
         /***  OJSoriginal code below
         self.iface.init()?;
@@ -84,17 +108,20 @@ where
         Command::DisplayOn(true).send(&mut self.iface)?;
 
         ******/
-        let display_rotation = self.display_rotation;
+        // All init commands go out as a single command stream
+        let mut batch = CommandBatch::new();
 
-        Command::DisplayOn(false).send(&mut self.iface)?;
-        Command::DisplayClockDiv(0x8, 0x0).send(&mut self.iface)?;
+        batch
+            .push(Command::DisplayOn(false))
+            .push(Command::DisplayClockDiv(self.clock_div.0, self.clock_div.1));
         let (_, display_height) = self.display_size.dimensions();
-        Command::Multiplex(display_height - 1).send(&mut self.iface)?;
-        Command::DisplayOffset(0).send(&mut self.iface)?;
-        Command::StartLine(0).send(&mut self.iface)?;
-        // TODO: Ability to turn charge pump on/off
-        Command::ChargePump(true).send(&mut self.iface)?;
-        Command::AddressMode(AddrMode::Horizontal).send(&mut self.iface)?;
+        batch
+            .push(Command::Multiplex(display_height - 1))
+            .push(Command::DisplayOffset(self.display_offset))
+            .push(Command::StartLine(self.start_line))
+            // TODO: Ability to turn charge pump on/off
+            .push(Command::ChargePump(true))
+            .push(Command::AddressMode(self.addr_mode));
 
         // self.size.configure(&mut self.interface)?;
         // match self.display_size {
@@ -102,27 +129,21 @@ where
         //     DisplaySize::Display128x64  | DisplaySize::Display128x64NoOffset
         //         | DisplaySize::Display132x64 => Command::ComPinConfig(true).send(&mut self.iface),
         // }?;
-        match self.display_size {
-            DisplaySize::Display128x32 => Command::ComPinConfig(false, false).send(&mut self.iface),
-            DisplaySize::Display128x64NoOffset => Command::ComPinConfig(true, false).send(&mut self.iface),
-            DisplaySize::Display128x64 => Command::ComPinConfig(true, false).send(&mut self.iface),
-            DisplaySize::Display132x64 => Command::ComPinConfig(true, false).send(&mut self.iface), // ?
-        } ?;
+        batch.push(self.com_pin_config());
 
+        self.push_rotation(&mut batch);
+        Self::push_brightness(&mut batch, self.brightness);
+        batch
+            .push(Command::VcomhDeselect(VcomhLevel::Auto))
+            .push(Command::AllOn(false))
+            .push(Command::Invert(self.invert))
+            .push(Command::EnableScroll(false))
+            .push(Command::ZoomIn(self.zoom))
+            .push(Command::DisplayOn(self.display_on));
 
-        log::debug!("init_column_mode, rotation.");
-        self.set_rotation(display_rotation)?;
-        log::debug!("init_column_mode, brightness.");
-        self.set_brightness(Brightness::default())?;
-        Command::VcomhDeselect(VcomhLevel::Auto).send(&mut self.iface)?;
-        Command::AllOn(false).send(&mut self.iface)?;
-        Command::Invert(false).send(&mut self.iface)?;
-        log::debug!("init_column_mode, EnableScroll.");
-        Command::EnableScroll(false).send(&mut self.iface)?;
-        log::debug!("init_column_mode, DisplayOn.");
-        Command::DisplayOn(true).send(&mut self.iface)?;
+        log::debug!("init_column_mode, sending init commands.");
+        batch.send(&mut self.iface)?;
 
-        self.addr_mode = AddrMode::Horizontal;
         log::debug!("init_column_mode, done.");
 
         Ok(())
@@ -130,8 +151,46 @@ where
 
     /// Change the display brightness.
     pub fn set_brightness(&mut self, brightness: Brightness) -> Result<(), DI::Error> {
-        Command::PreChargePeriod(1, brightness.precharge).send(&mut self.iface)?;
-        Command::Contrast(brightness.contrast).send(&mut self.iface)
+        self.brightness = brightness;
+        self.send_brightness(brightness)
+    }
+
+    /// Get the last brightness set with `set_brightness` or `set_contrast`
+    pub fn get_brightness(&self) -> Brightness {
+        self.brightness
+    }
+
+    /// Send a brightness to the display without recording it, e.g. to dim temporarily
+    pub(crate) fn send_brightness(&mut self, brightness: Brightness) -> Result<(), DI::Error> {
+        let mut batch = CommandBatch::new();
+        Self::push_brightness(&mut batch, brightness);
+        batch.send(&mut self.iface)
+    }
+
+    fn push_brightness(batch: &mut CommandBatch, brightness: Brightness) {
+        batch
+            .push(Command::PreChargePeriod(1, brightness.precharge))
+            .push(Command::Contrast(brightness.contrast));
+    }
+
+    /// Get the memory addressing mode
+    pub fn get_addressing_mode(&self) -> AddrMode {
+        self.addr_mode
+    }
+
+    /// Set the memory addressing mode. This changes the order `draw` expects data in:
+    ///
+    /// - [`AddrMode::Horizontal`]: one page (8 rows) at a time, column by column from left to
+    ///   right, then the next page down. The display wraps around the draw area on its own.
+    /// - [`AddrMode::Vertical`]: one column at a time, page by page from top to bottom, then the
+    ///   next column to the right. The display wraps around the draw area on its own.
+    /// - [`AddrMode::Page`]: the same order as horizontal mode, but the driver sends a new address
+    ///   for every page.
+    ///
+    /// The draw area has to be set again after changing the mode.
+    pub fn set_addressing_mode(&mut self, addr_mode: AddrMode) -> Result<(), DI::Error> {
+        self.addr_mode = addr_mode;
+        Command::AddressMode(addr_mode).send(&mut self.iface)
     }
 
     /// Set the position in the framebuffer of the display where any sent data should be
@@ -148,12 +207,66 @@ where
 
     /// Send the data to the display for drawing at the current position in the framebuffer
     /// and advance the position accordingly. Cf. `set_draw_area` to modify the affected area by
-    /// this method.
+    /// this method and `set_addressing_mode` for the expected data order.
+    ///
+    /// If sending fails and the interface allows a retry, the draw address is set again to the
+    /// position after the data that reached the display before the rest is sent.
     pub fn draw(&mut self, mut buffer: &[u8]) -> Result<(), DI::Error> {
+        let mut attempt = 1;
+
+        if self.addr_mode != AddrMode::Page {
+            while !buffer.is_empty() {
+                // The display wraps around the draw area on its own, the whole buffer can be
+                // sent in one go. A window narrowed to resume a transfer only covers the rest of
+                // the current line.
+                let count = if self.narrowed {
+                    self.line_remaining().min(buffer.len())
+                } else {
+                    buffer.len()
+                };
+
+                match self.iface.send_data(&buffer[..count]) {
+                    Ok(()) => {
+                        self.advance_draw_position(count);
+                        buffer = &buffer[count..];
+                        if self.narrowed && self.at_line_start() {
+                            self.send_draw_address()?;
+                        }
+                    }
+                    Err(error) => {
+                        // Keep track of what made it to the display so the transfer can be
+                        // resumed
+                        let sent = self.iface.data_sent().unwrap_or(0);
+                        self.advance_draw_position(sent);
+                        buffer = &buffer[sent..];
+
+                        if !self.iface.retry(&error, attempt) {
+                            return Err(error);
+                        }
+                        attempt = attempt.saturating_add(1);
+                        // Bytes of the failed chunk may have moved the RAM pointer
+                        self.send_draw_address()?;
+                    }
+                }
+            }
+            return Ok(());
+        }
+
         while !buffer.is_empty() {
-            let count = self.draw_area_end.0 - self.draw_column;
-            self.iface.send_data(&buffer[..count as usize])?;
-            self.draw_column += count;
+            let count = ((self.draw_area_end.0 - self.draw_column) as usize).min(buffer.len());
+            if let Err(error) = self.iface.send_data(&buffer[..count]) {
+                let sent = self.iface.data_sent().unwrap_or(0);
+                self.draw_column += sent as u8;
+                buffer = &buffer[sent..];
+
+                if !self.iface.retry(&error, attempt) {
+                    return Err(error);
+                }
+                attempt = attempt.saturating_add(1);
+                self.send_draw_address()?;
+                continue;
+            }
+            self.draw_column += count as u8;
 
             if self.draw_column >= self.draw_area_end.0 {
                 self.draw_column = self.draw_area_start.0;
@@ -166,16 +279,106 @@ where
                 self.send_draw_address()?;
             }
 
-            buffer = &buffer[count as usize..];
+            buffer = &buffer[count..];
         }
 
         Ok(())
     }
 
+    /// Width in columns and height in pages of the draw area
+    fn draw_area_size(&self) -> (usize, usize) {
+        (
+            (self.draw_area_end.0 - self.draw_area_start.0) as usize,
+            (self.draw_area_end.1 - self.draw_area_start.1).div_ceil(8) as usize,
+        )
+    }
+
+    /// Number of bytes drawn since the start of the draw area, i.e. the offset into the buffer
+    /// passed to `draw` that the next byte will be taken from
+    pub fn draw_offset(&self) -> usize {
+        let (width, pages) = self.draw_area_size();
+        let column = (self.draw_column - self.draw_area_start.0) as usize;
+        let page = ((self.draw_row - self.draw_area_start.1) / 8) as usize;
+
+        match self.addr_mode {
+            AddrMode::Horizontal | AddrMode::Page => page * width + column,
+            AddrMode::Vertical => column * pages + page,
+        }
+    }
+
+    /// Track the position the display moved to after `len` bytes were sent in horizontal or
+    /// vertical mode
+    fn advance_draw_position(&mut self, len: usize) {
+        let (width, pages) = self.draw_area_size();
+        if width == 0 || pages == 0 {
+            return;
+        }
+
+        let position = (self.draw_offset() + len) % (width * pages);
+        let (column, page) = match self.addr_mode {
+            AddrMode::Horizontal | AddrMode::Page => (position % width, position / width),
+            AddrMode::Vertical => (position / pages, position % pages),
+        };
+
+        self.draw_column = self.draw_area_start.0 + column as u8;
+        self.draw_row = self.draw_area_start.1 + (page * 8) as u8;
+    }
+
+    /// Whether the position is at the start of a line of the draw area, a page in horizontal and
+    /// page mode or a column in vertical mode
+    fn at_line_start(&self) -> bool {
+        match self.addr_mode {
+            AddrMode::Horizontal | AddrMode::Page => self.draw_column == self.draw_area_start.0,
+            AddrMode::Vertical => self.draw_row == self.draw_area_start.1,
+        }
+    }
+
+    /// Number of bytes left in the current line of the draw area
+    fn line_remaining(&self) -> usize {
+        match self.addr_mode {
+            AddrMode::Horizontal | AddrMode::Page => {
+                (self.draw_area_end.0 - self.draw_column) as usize
+            }
+            AddrMode::Vertical => (self.draw_area_end.1 - self.draw_row).div_ceil(8) as usize,
+        }
+    }
+
+    /// Send the address of the current position. In horizontal and vertical mode the window
+    /// starts at the position, so it is narrowed to the rest of the line when resuming mid-line.
     fn send_draw_address(&mut self) -> Result<(), DI::Error> {
-        Command::PageStart(self.draw_row.into()).send(&mut self.iface)?;
-        Command::ColumnAddressLow(0xF & self.draw_column).send(&mut self.iface)?;
-        Command::ColumnAddressHigh(0xF & (self.draw_column >> 4)).send(&mut self.iface)
+        self.narrowed = self.addr_mode != AddrMode::Page && !self.at_line_start();
+
+        match self.addr_mode {
+            // The column window is in SSD1306 columns 0-127. The SH1106-style column offset of
+            // the display size doesn't apply to it, the SSD1306 has no hidden columns.
+            AddrMode::Horizontal | AddrMode::Vertical => CommandBatch::new()
+                .push(Command::ColumnAddress(
+                    self.ssd1306_column(self.draw_column),
+                    self.ssd1306_column(self.draw_area_end.0 - 1),
+                ))
+                .push(Command::PageAddress(
+                    self.draw_row.into(),
+                    (self.draw_area_end.1 - 1).into(),
+                ))
+                .send(&mut self.iface),
+            AddrMode::Page => CommandBatch::new()
+                .push(Command::PageStart(self.draw_row.into()))
+                .push(Command::ColumnAddressLow(
+                    0xF & self.ssd1306_column(self.draw_column),
+                ))
+                .push(Command::ColumnAddressHigh(
+                    0xF & (self.ssd1306_column(self.draw_column) >> 4),
+                ))
+                .send(&mut self.iface),
+        }
+    }
+
+    /// Map a draw area column, which includes the column offset of the display size, to an
+    /// SSD1306 column address
+    fn ssd1306_column(&self, column: u8) -> u8 {
+        column
+            .saturating_sub(self.display_size.column_offset())
+            .min(MAX_COLUMN)
     }
 
     /// Get the configured display size
@@ -183,9 +386,13 @@ where
         self.display_size
     }
 
-    /// Get display dimensions, taking into account the current rotation of the display
+    /// Get display dimensions, taking into account the current rotation and zoom of the display.
+    /// Mirroring never changes the dimensions.
     pub fn get_dimensions(&self) -> (u8, u8) {
-        let (w, h) = self.display_size.dimensions();
+        let (w, mut h) = self.display_size.dimensions();
+        if self.zoom {
+            h /= 2;
+        }
 
         match self.display_rotation {
             DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (w, h),
@@ -202,28 +409,248 @@ where
     pub fn set_rotation(&mut self, display_rotation: DisplayRotation) -> Result<(), DI::Error> {
         self.display_rotation = display_rotation;
 
-        match display_rotation {
-            DisplayRotation::Rotate0 => {
-                Command::SegmentRemap(true).send(&mut self.iface)?;
-                Command::ReverseComDir(true).send(&mut self.iface)
-            }
-            DisplayRotation::Rotate90 => {
-                Command::SegmentRemap(false).send(&mut self.iface)?;
-                Command::ReverseComDir(true).send(&mut self.iface)
-            }
-            DisplayRotation::Rotate180 => {
-                Command::SegmentRemap(false).send(&mut self.iface)?;
-                Command::ReverseComDir(false).send(&mut self.iface)
+        let mut batch = CommandBatch::new();
+        self.push_rotation(&mut batch);
+        batch.send(&mut self.iface)
+    }
+
+    /// Get the horizontal and vertical mirroring of the display
+    pub fn get_mirror(&self) -> (bool, bool) {
+        self.mirror
+    }
+
+    /// Mirror the display horizontally and/or vertically, in the coordinates of the current
+    /// rotation. Mirroring is done by the display hardware and combined with the rotation.
+    pub fn set_mirror(&mut self, horizontal: bool, vertical: bool) -> Result<(), DI::Error> {
+        self.mirror = (horizontal, vertical);
+
+        let mut batch = CommandBatch::new();
+        self.push_rotation(&mut batch);
+        batch.send(&mut self.iface)
+    }
+
+    /// Set the mirroring applied by the next `init_column_mode` without sending any commands
+    pub(crate) fn with_mirror(self, horizontal: bool, vertical: bool) -> Self {
+        Self {
+            mirror: (horizontal, vertical),
+            ..self
+        }
+    }
+
+    /// Queue the segment remap and COM direction commands for the current rotation and mirroring
+    fn push_rotation(&self, batch: &mut CommandBatch) {
+        let (mut remap, mut reverse) = match self.display_rotation {
+            DisplayRotation::Rotate0 => (true, true),
+            DisplayRotation::Rotate90 => (false, true),
+            DisplayRotation::Rotate180 => (false, false),
+            DisplayRotation::Rotate270 => (true, false),
+        };
+
+        // Segment remap flips panel columns and the COM direction flips panel rows, which are
+        // the logical rows and columns respectively when rotated by 90 or 270 degrees
+        let (horizontal, vertical) = self.mirror;
+        match self.display_rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                remap ^= horizontal;
+                reverse ^= vertical;
             }
-            DisplayRotation::Rotate270 => {
-                Command::SegmentRemap(true).send(&mut self.iface)?;
-                Command::ReverseComDir(false).send(&mut self.iface)
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                remap ^= vertical;
+                reverse ^= horizontal;
             }
         }
+
+        batch
+            .push(Command::SegmentRemap(remap))
+            .push(Command::ReverseComDir(reverse));
+    }
+
+    /// Get whether zoom-in (double height) mode is active
+    pub fn get_zoom(&self) -> bool {
+        self.zoom
+    }
+
+    /// Enable or disable zoom-in mode. While active, each row in the upper half of the display
+    /// RAM is shown twice, so the logical display height is halved.
+    ///
+    /// Zoom-in only works with the alternative COM pin configuration, so 128x32 panels, which
+    /// otherwise use the sequential one, are switched over while zoomed.
+    pub fn set_zoom(&mut self, zoom: bool) -> Result<(), DI::Error> {
+        self.zoom = zoom;
+
+        let mut batch = CommandBatch::new();
+        batch
+            .push(self.com_pin_config())
+            .push(Command::ZoomIn(zoom));
+        batch.send(&mut self.iface)
+    }
+
+    /// COM pin configuration for the display size, using the alternative configuration required
+    /// by zoom-in mode while zoomed
+    fn com_pin_config(&self) -> Command {
+        match self.display_size {
+            DisplaySize::Display128x32 => Command::ComPinConfig(self.zoom, false),
+            DisplaySize::Display128x64NoOffset => Command::ComPinConfig(true, false),
+            DisplaySize::Display128x64 => Command::ComPinConfig(true, false),
+            DisplaySize::Display132x64 => Command::ComPinConfig(true, false), // ?
+        }
     }
 
     /// Set the display contrast
     pub fn set_contrast(&mut self, contrast: u8) -> Result<(), DI::Error> {
+        self.brightness.contrast = contrast;
         Command::Contrast(contrast).send(&mut self.iface)
     }
+
+    /// Set the vertical display offset in rows. The image wraps around the 64 rows of display RAM.
+    pub fn set_display_offset(&mut self, offset: u8) -> Result<(), DI::Error> {
+        self.display_offset = 0x3F & offset;
+        Command::DisplayOffset(self.display_offset).send(&mut self.iface)
+    }
+
+    /// Set the display clock. `fosc` is the oscillator frequency setting (0-15, higher is faster)
+    /// and `div` the clock divide ratio minus one (0-15).
+    pub fn set_clock_div(&mut self, fosc: u8, div: u8) -> Result<(), DI::Error> {
+        self.clock_div = (fosc, div);
+        Command::DisplayClockDiv(fosc, div).send(&mut self.iface)
+    }
+
+    /// Turn the display panel on or off. Display RAM content is kept while off.
+    pub fn set_display_on(&mut self, on: bool) -> Result<(), DI::Error> {
+        self.display_on = on;
+        Command::DisplayOn(on).send(&mut self.iface)
+    }
+
+    /// Get the display RAM row shown on the top row of the display
+    pub fn get_start_line(&self) -> u8 {
+        self.start_line
+    }
+
+    /// Set the display RAM row shown on the top row of the display (0-63). Rows wrap around the
+    /// 64 rows of display RAM, so this scrolls the image vertically without sending any data.
+    pub fn set_start_line(&mut self, line: u8) -> Result<(), DI::Error> {
+        self.start_line = 0x3F & line;
+        Command::StartLine(self.start_line).send(&mut self.iface)
+    }
+
+    /// Get whether the display colours are inverted
+    pub fn get_invert(&self) -> bool {
+        self.invert
+    }
+
+    /// Invert the display colours, i.e. show set pixels as off and cleared pixels as on
+    pub fn set_invert(&mut self, invert: bool) -> Result<(), DI::Error> {
+        self.invert = invert;
+        Command::Invert(invert).send(&mut self.iface)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::mock::{MockHandle, MockInterface};
+
+    fn properties(addr_mode: AddrMode) -> (DisplayProperties<MockInterface>, MockHandle) {
+        let (iface, state) = MockInterface::new();
+        let mut properties = DisplayProperties::new(
+            iface,
+            DisplaySize::Display128x64NoOffset,
+            DisplayRotation::Rotate0,
+            addr_mode,
+        );
+        properties.set_addressing_mode(addr_mode).unwrap();
+        properties.set_draw_area((0, 0), (128, 64)).unwrap();
+
+        (properties, state)
+    }
+
+    #[test]
+    fn failed_draw_tracks_complete_chunks() {
+        for addr_mode in [AddrMode::Horizontal, AddrMode::Vertical, AddrMode::Page] {
+            for (fail_after, sent) in [(0, 0), (31, 0), (100, 96), (128, 128), (300, 288)] {
+                let (mut properties, state) = properties(addr_mode);
+                state.borrow_mut().fail_data_after = Some(fail_after);
+
+                assert!(properties.draw(&[0xAA; 1024]).is_err());
+                // Page mode sends a transfer per page, chunks restart at every page
+                let sent = match addr_mode {
+                    AddrMode::Page => fail_after / 128 * 128 + fail_after % 128 / 32 * 32,
+                    _ => sent,
+                };
+                assert_eq!(properties.draw_offset(), sent, "{addr_mode:?} {fail_after}");
+                assert_eq!(state.borrow().data_bytes, fail_after);
+            }
+        }
+    }
+
+    #[test]
+    fn retried_draw_resumes_at_draw_position() {
+        for addr_mode in [AddrMode::Horizontal, AddrMode::Vertical, AddrMode::Page] {
+            for fail_after in [0, 31, 100, 128, 300, 1023] {
+                let (mut properties, state) = properties(addr_mode);
+                state.borrow_mut().max_attempts = 2;
+                state.borrow_mut().fail_data_after = Some(fail_after);
+
+                let data: [u8; 1024] = core::array::from_fn(|i| (i * 7 + i / 128) as u8);
+                properties.draw(&data).unwrap();
+
+                let state = state.borrow();
+                for page in 0..8 {
+                    for column in 0..128 {
+                        let expected = match addr_mode {
+                            AddrMode::Vertical => data[column * 8 + page],
+                            _ => data[page * 128 + column],
+                        };
+                        assert_eq!(
+                            state.ram[page][column], expected,
+                            "{addr_mode:?} {fail_after} page {page} column {column}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn retried_command_is_resynchronised() {
+        for fail_after in 0..3 {
+            let (mut properties, state) = properties(AddrMode::Horizontal);
+            state.borrow_mut().max_attempts = 2;
+            state.borrow_mut().fail_commands_after = Some(fail_after);
+
+            properties.set_contrast(0x40).unwrap();
+            properties.set_invert(true).unwrap();
+
+            let state = state.borrow();
+            assert_eq!(state.contrast, 0x40, "{fail_after}");
+            assert_eq!(state.commands.last().unwrap()[..], [0xA7]);
+        }
+    }
+
+    #[test]
+    fn column_window_ignores_column_offset() {
+        for addr_mode in [AddrMode::Horizontal, AddrMode::Page] {
+            let (iface, state) = MockInterface::new();
+            let mut properties = DisplayProperties::new(
+                iface,
+                DisplaySize::Display128x64,
+                DisplayRotation::Rotate0,
+                addr_mode,
+            );
+            properties.set_addressing_mode(addr_mode).unwrap();
+            properties.set_draw_area((2, 0), (130, 64)).unwrap();
+            if addr_mode == AddrMode::Horizontal {
+                assert_eq!(state.borrow().window, ((0, 127), (0, 7)));
+            }
+
+            let data: [u8; 1024] = core::array::from_fn(|i| (i % 128) as u8 + 1);
+            properties.draw(&data).unwrap();
+
+            let state = state.borrow();
+            for page in 0..8 {
+                assert_eq!(state.ram[page][0], 1, "{addr_mode:?} page {page}");
+                assert_eq!(state.ram[page][127], 128, "{addr_mode:?} page {page}");
+            }
+        }
+    }
 }
@@ -0,0 +1,36 @@
+//! Raster operations
+//!
+//! A [`RasterOp`] decides how drawn pixels combine with the pixels already in a buffer. With
+//! [`RasterOp::Xor`], drawing the same shape twice restores the original content, which is handy
+//! for cursors and selections.
+
+/// How a drawn (source) pixel combines with the pixel in the buffer (destination)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RasterOp {
+    /// The source replaces the destination
+    #[default]
+    Copy,
+    /// Pixels that are on in the source are turned on
+    Or,
+    /// Pixels that are off in the source are turned off
+    And,
+    /// Pixels that are on in the source are toggled
+    Xor,
+    /// Pixels that are on in the source are turned off
+    AndNot,
+}
+
+impl RasterOp {
+    /// Combine the bits of `src` selected by `mask` into `dst`. Bits outside `mask` are kept.
+    pub fn apply(self, dst: u8, src: u8, mask: u8) -> u8 {
+        let result = match self {
+            RasterOp::Copy => src,
+            RasterOp::Or => dst | src,
+            RasterOp::And => dst & src,
+            RasterOp::Xor => dst ^ src,
+            RasterOp::AndNot => dst & !src,
+        };
+
+        (dst & !mask) | (result & mask)
+    }
+}
@@ -0,0 +1,202 @@
+//! Several displays combined into one drawing target
+//!
+//! A [`TiledDisplay`] places `N` [`GraphicsMode`] displays, e.g. on different I2C addresses of a
+//! shared bus, at fixed positions of one large drawing area. Each tile keeps its own rotation and
+//! other settings. Drawing goes to the tiles covering each pixel, and `flush` only sends the tiles
+//! that were drawn to.
+//!
+//! All tiles use the same interface type. Displays on a shared bus can use a bus sharing wrapper
+//! such as the ones from `embedded-hal-bus`.
+//!
+//! ```rust,ignore
+//! use ssd1306_i2c::tiled::TiledDisplay;
+//!
+//! let left: GraphicsMode<_> = Builder::new().with_i2c_addr(0x3c).connect_i2c(bus_a).into();
+//! let right: GraphicsMode<_> = Builder::new().with_i2c_addr(0x3d).connect_i2c(bus_b).into();
+//!
+//! let mut panel = TiledDisplay::horizontal([left, right]);
+//! panel.init().unwrap();
+//! // draw across both displays ...
+//! panel.flush().unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    pixelcolor::BinaryColor,
+    primitives::Rectangle,
+    Pixel,
+};
+
+use crate::{interface::DisplayInterface, mode::GraphicsMode};
+
+/// `N` displays combined into one drawing target
+pub struct TiledDisplay<DI, const N: usize>
+where
+    DI: DisplayInterface,
+{
+    tiles: [GraphicsMode<DI>; N],
+    origins: [Point; N],
+    dirty: [bool; N],
+}
+
+impl<DI, const N: usize> TiledDisplay<DI, N>
+where
+    DI: DisplayInterface,
+{
+    /// Combine displays with the top left corner of each at the matching position in `origins`.
+    /// Tiles should not overlap, overlapping pixels are drawn to every tile covering them.
+    pub fn new(tiles: [GraphicsMode<DI>; N], origins: [Point; N]) -> Self {
+        TiledDisplay {
+            tiles,
+            origins,
+            dirty: [false; N],
+        }
+    }
+
+    /// Combine displays side by side from left to right, using the dimensions of each display
+    /// after its rotation
+    pub fn horizontal(tiles: [GraphicsMode<DI>; N]) -> Self {
+        let mut origins = [Point::zero(); N];
+        let mut x = 0;
+        for (tile, origin) in tiles.iter().zip(origins.iter_mut()) {
+            *origin = Point::new(x, 0);
+            x += tile.get_dimensions().0 as i32;
+        }
+
+        Self::new(tiles, origins)
+    }
+
+    /// Combine displays on top of each other from top to bottom, using the dimensions of each
+    /// display after its rotation
+    pub fn vertical(tiles: [GraphicsMode<DI>; N]) -> Self {
+        let mut origins = [Point::zero(); N];
+        let mut y = 0;
+        for (tile, origin) in tiles.iter().zip(origins.iter_mut()) {
+            *origin = Point::new(0, y);
+            y += tile.get_dimensions().1 as i32;
+        }
+
+        Self::new(tiles, origins)
+    }
+
+    /// Initialise all displays
+    pub fn init(&mut self) -> Result<(), DI::Error> {
+        for tile in self.tiles.iter_mut() {
+            tile.init()?;
+        }
+        Ok(())
+    }
+
+    /// Clear the buffers of all displays. This method does not update the displays themselves.
+    pub fn clear(&mut self) {
+        for tile in self.tiles.iter_mut() {
+            tile.clear();
+        }
+        self.dirty = [true; N];
+    }
+
+    /// Send the buffers of the displays that were drawn to since the last flush. A display that
+    /// fails to update stays marked for the next flush.
+    pub fn flush(&mut self) -> Result<(), DI::Error> {
+        for (tile, dirty) in self.tiles.iter_mut().zip(self.dirty.iter_mut()) {
+            if *dirty {
+                tile.flush()?;
+                *dirty = false;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the display at `index`
+    pub fn tile(&self, index: usize) -> Option<&GraphicsMode<DI>> {
+        self.tiles.get(index)
+    }
+
+    /// Get the display at `index` to change its settings or draw into it directly. It is flushed
+    /// on the next `flush`.
+    pub fn tile_mut(&mut self, index: usize) -> Option<&mut GraphicsMode<DI>> {
+        let tile = self.tiles.get_mut(index)?;
+        self.dirty[index] = true;
+
+        Some(tile)
+    }
+
+    /// Get the area covered by the display at `index`
+    pub fn tile_area(&self, index: usize) -> Option<Rectangle> {
+        let tile = self.tiles.get(index)?;
+
+        Some(Rectangle::new(self.origins[index], tile.size()))
+    }
+
+    /// Release the displays
+    pub fn release(self) -> [GraphicsMode<DI>; N] {
+        self.tiles
+    }
+}
+
+impl<DI, const N: usize> DrawTarget for TiledDisplay<DI, N>
+where
+    DI: DisplayInterface,
+{
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(pos, color) in pixels {
+            for ((tile, origin), dirty) in self
+                .tiles
+                .iter_mut()
+                .zip(self.origins.iter())
+                .zip(self.dirty.iter_mut())
+            {
+                let local = pos - *origin;
+                if tile.bounding_box().contains(local) {
+                    tile.set_pixel(local.x as u32, local.y as u32, color.is_on().into());
+                    *dirty = true;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        for ((tile, origin), dirty) in self
+            .tiles
+            .iter_mut()
+            .zip(self.origins.iter())
+            .zip(self.dirty.iter_mut())
+        {
+            // The area in the coordinates of the tile
+            let local = Rectangle::new(area.top_left - *origin, area.size)
+                .intersection(&tile.bounding_box());
+            if !local.is_zero_sized() {
+                tile.fill_solid(&local, color)?;
+                *dirty = true;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, const N: usize> OriginDimensions for TiledDisplay<DI, N>
+where
+    DI: DisplayInterface,
+{
+    /// The size of the area from the origin to the bottom right corner of the furthest tile
+    fn size(&self) -> Size {
+        let mut size = Size::zero();
+        for (tile, origin) in self.tiles.iter().zip(self.origins.iter()) {
+            let (width, height) = tile.get_dimensions();
+            size.width = size.width.max((origin.x + width as i32).max(0) as u32);
+            size.height = size.height.max((origin.y + height as i32).max(0) as u32);
+        }
+
+        size
+    }
+}
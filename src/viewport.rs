@@ -0,0 +1,117 @@
+//! Split-screen viewports
+//!
+//! A [`Viewport`] is a part of a [`GraphicsMode`] display with its own origin and clipping, so
+//! independent parts of an application, e.g. a status bar and a content area, can draw without
+//! knowing where on the screen they are. The display tracks which columns of each page of display
+//! RAM changed, so every viewport knows whether it has unsent changes, even after it was dropped
+//! and created again. Flushing a viewport only sends its own pages and columns.
+//!
+//! ```rust,ignore
+//! let mut status = display.viewport(Rectangle::new(Point::zero(), Size::new(128, 8)));
+//! Text::with_baseline("12:00", Point::zero(), style, Baseline::Top)
+//!     .draw(&mut status)
+//!     .unwrap();
+//! status.flush().unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Size},
+    pixelcolor::BinaryColor,
+    primitives::Rectangle,
+    Pixel,
+};
+
+use crate::{interface::DisplayInterface, mode::GraphicsMode};
+
+/// A clipped and translated drawing target on part of a [`GraphicsMode`]
+pub struct Viewport<'a, DI>
+where
+    DI: DisplayInterface,
+{
+    display: &'a mut GraphicsMode<DI>,
+    area: Rectangle,
+}
+
+impl<'a, DI> Viewport<'a, DI>
+where
+    DI: DisplayInterface,
+{
+    /// Create a viewport on `area` of the display, clipped to the display
+    pub fn new(display: &'a mut GraphicsMode<DI>, area: Rectangle) -> Self {
+        let area = area.intersection(&display.bounding_box());
+
+        Viewport { display, area }
+    }
+
+    /// Get the area of the display covered by the viewport
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+
+    /// Whether the viewport changed since it was last sent. Changes are tracked a byte at a time,
+    /// so pixels drawn just above or below the viewport in the same page count as well.
+    pub fn is_dirty(&self) -> bool {
+        self.display.is_area_dirty(self.area)
+    }
+
+    /// Fill the viewport with `color`
+    pub fn clear_with(&mut self, color: BinaryColor) {
+        // Infallible
+        let _ = self.display.fill_solid(&self.area, color);
+    }
+
+    /// Send the pages and columns covered by the viewport to the display if they changed, see
+    /// [`GraphicsMode::flush_viewport`]
+    pub fn flush(&mut self) -> Result<(), DI::Error> {
+        if !self.is_dirty() {
+            return Ok(());
+        }
+
+        self.display.flush_viewport(self.area)
+    }
+}
+
+impl<DI> DrawTarget for Viewport<'_, DI>
+where
+    DI: DisplayInterface,
+{
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bb = self.bounding_box();
+        let origin = self.area.top_left;
+
+        self.display.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(pos, _color)| bb.contains(*pos))
+                .map(|Pixel(pos, color)| Pixel(pos + origin, color)),
+        )?;
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        // Clip to the viewport, then move to display coordinates
+        let area = area.intersection(&self.bounding_box());
+        let area = Rectangle::new(area.top_left + self.area.top_left, area.size);
+
+        self.display.fill_solid(&area, color)?;
+
+        Ok(())
+    }
+}
+
+impl<DI> OriginDimensions for Viewport<'_, DI>
+where
+    DI: DisplayInterface,
+{
+    fn size(&self) -> Size {
+        self.area.size
+    }
+}